@@ -1,6 +1,6 @@
 //! HTML content extractors
 
-use scraper::{Html, Selector};
+use scraper::{Html, Node, Selector};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -15,7 +15,7 @@ pub struct ExtractionRequest {
 }
 
 /// Single extraction specification
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ExtractSpec {
     /// Source type: jsonld, microdata, og, meta, css
     pub source: String,
@@ -48,6 +48,20 @@ pub struct ExtractSpec {
     /// Additional JSON path after cast (e.g., ->'items')
     #[serde(default)]
     pub json_path: Option<String>,
+    /// Child specs evaluated relative to this spec's scope, assembled into a
+    /// nested JSON object under this spec's alias.
+    #[serde(default)]
+    pub children: Vec<ExtractSpec>,
+    /// CSS selector narrowing the context for `children` (DOM scope).
+    #[serde(default)]
+    pub scope_selector: Option<String>,
+    /// Structured-data path narrowing the context for `children` (JSON scope).
+    #[serde(default)]
+    pub scope_path: Vec<String>,
+    /// Handlebars-style template for `source: "template"` specs, interpolating
+    /// other aliases. Falls back to `selector` when unset.
+    #[serde(default)]
+    pub template: Option<String>,
 }
 
 /// Extraction result
@@ -79,6 +93,34 @@ pub fn extract_all(html: &str, request: &ExtractionRequest) -> ExtractionResult
     let js_data = extract_js_variables(&document);
 
     for spec in &request.specs {
+        // Template specs are rendered in a second pass so they can reference the
+        // aliases produced by every other spec.
+        if spec.source == "template" {
+            continue;
+        }
+
+        // Specs with children build a nested JSON document, assembled under the
+        // spec's alias rather than producing a flat scalar.
+        if !spec.children.is_empty() {
+            let nested = extract_nested(
+                &document, spec, &jsonld_data, &microdata, &og_data, &meta_data, &js_data,
+            );
+            values.insert(spec.alias.clone(), Some(nested.to_string()));
+            continue;
+        }
+
+        // `Type[*]` on a structured-data source expands every matching entity
+        // into one row, mirroring `expand_array` for embedded JSON.
+        if matches!(spec.source.as_str(), "jsonld" | "microdata")
+            && spec.path.first().map(|s| s.contains("[*]")).unwrap_or(false)
+        {
+            let data = if spec.source == "jsonld" { &jsonld_data } else { &microdata };
+            let expanded = extract_typed_expanded(data, &spec.path, spec.return_text);
+            expanded_values.insert(spec.alias.clone(), expanded);
+            values.insert(spec.alias.clone(), None);
+            continue;
+        }
+
         let raw_value = extract_single(
             &document,
             spec,
@@ -158,11 +200,151 @@ pub fn extract_all(html: &str, request: &ExtractionRequest) -> ExtractionResult
         }
     }
 
+    // Second pass: render template specs against the accumulated results.
+    let template_specs: Vec<&ExtractSpec> = request
+        .specs
+        .iter()
+        .filter(|s| s.source == "template")
+        .collect();
+    if !template_specs.is_empty() {
+        let mut context = build_template_context(&values, &expanded_values);
+        for spec in template_specs {
+            let template = spec
+                .template
+                .as_deref()
+                .or(spec.selector.as_deref())
+                .unwrap_or("");
+            let rendered = render_template(template, &context);
+            // Expose this alias to later templates.
+            context.insert(spec.alias.clone(), Value::String(rendered.clone()));
+            values.insert(spec.alias.clone(), Some(rendered));
+        }
+    }
+
     ExtractionResult { values, expanded_values, error: None }
 }
 
-/// Navigate a JSON value using arrow notation path
+/// Assemble a JSON context for template rendering from extracted results.
+///
+/// Scalar values are parsed as JSON when possible (so dotted paths reach into
+/// structured values) and fall back to strings; expanded specs become arrays.
+fn build_template_context(
+    values: &HashMap<String, Option<String>>,
+    expanded_values: &HashMap<String, Vec<String>>,
+) -> serde_json::Map<String, Value> {
+    let mut context = serde_json::Map::new();
+
+    for (alias, value) in values {
+        if let Some(raw) = value {
+            let parsed = serde_json::from_str::<Value>(raw).unwrap_or_else(|_| Value::String(raw.clone()));
+            context.insert(alias.clone(), parsed);
+        }
+    }
+
+    for (alias, items) in expanded_values {
+        let arr = items.iter().map(|s| Value::String(s.clone())).collect();
+        context.insert(alias.clone(), Value::Array(arr));
+    }
+
+    context
+}
+
+/// Render a Handlebars-style template against a JSON context.
+///
+/// Supports `{{ path }}` mustache interpolation (dotted paths and array
+/// indices resolved via the JSON navigator), `{{#each arr}} ... {{this}} ...
+/// {{/each}}` blocks with an optional `{{else}}` branch, and renders missing
+/// paths as the empty string.
+fn render_template(template: &str, context: &serde_json::Map<String, Value>) -> String {
+    let ctx = Value::Object(context.clone());
+    render_template_scoped(template, &ctx)
+}
+
+/// Render a template fragment against a specific scope value (used for both the
+/// top-level context and each iteration of an `{{#each}}` block).
+fn render_template_scoped(template: &str, scope: &Value) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find("{{") {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let close = match after.find("}}") {
+            Some(c) => c,
+            None => {
+                out.push_str("{{");
+                rest = after;
+                continue;
+            }
+        };
+        let tag = after[..close].trim();
+        let tail = &after[close + 2..];
+
+        if let Some(expr) = tag.strip_prefix("#each ") {
+            // Find the matching {{/each}} (no nesting of each blocks supported).
+            if let Some(end) = tail.find("{{/each}}") {
+                let block = &tail[..end];
+                rest = &tail[end + "{{/each}}".len()..];
+                out.push_str(&render_each(expr.trim(), block, scope));
+                continue;
+            }
+            rest = tail;
+            continue;
+        }
+
+        out.push_str(&resolve_template_path(tag, scope));
+        rest = tail;
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Render an `{{#each}}` block, iterating an array (with `{{else}}` fallback).
+fn render_each(path: &str, block: &str, scope: &Value) -> String {
+    let (body, else_branch) = match block.find("{{else}}") {
+        Some(i) => (&block[..i], &block[i + "{{else}}".len()..]),
+        None => (block, ""),
+    };
+
+    let value = resolve_template_value(path, scope);
+    match value {
+        Some(Value::Array(items)) if !items.is_empty() => {
+            items.iter().map(|item| render_template_scoped(body, item)).collect()
+        }
+        _ => render_template_scoped(else_branch, scope),
+    }
+}
+
+/// Resolve a mustache path to its rendered string (empty when missing).
+fn resolve_template_path(path: &str, scope: &Value) -> String {
+    match resolve_template_value(path, scope) {
+        Some(Value::String(s)) => s,
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Resolve a mustache path to a JSON value against the current scope.
+fn resolve_template_value(path: &str, scope: &Value) -> Option<Value> {
+    if path == "this" {
+        return Some(scope.clone());
+    }
+    let segments = parse_segments(&format!(".{}", path));
+    navigate_json(scope, &segments).into_iter().next()
+}
+
+/// Navigate a JSON value.
+///
+/// When `path` begins with `$` it is evaluated as JSONPath (see
+/// [`jsonpath_query`]) and the matches are returned as a `Value::Array`, which
+/// composes with `expand_array` to yield one row per match. Otherwise the
+/// legacy Postgres-style `->`/`->>` arrow syntax is used.
 fn navigate_json_path(value: &Value, path: &str) -> Option<Value> {
+    if path.trim_start().starts_with('$') {
+        return Some(Value::Array(jsonpath_query(value, path.trim())));
+    }
+
     let mut current = value.clone();
     let mut remaining = path.trim();
 
@@ -212,6 +394,138 @@ fn navigate_json_path(value: &Value, path: &str) -> Option<Value> {
     Some(current)
 }
 
+/// A narrowed context for nested extraction: either a DOM sub-fragment or a
+/// sub-`Value` of pre-extracted structured data.
+enum Scope {
+    Dom(Html),
+    Json(Value),
+}
+
+/// Build a nested JSON document from a spec's `children`, evaluated relative to
+/// the spec's scope.
+///
+/// The scope is a set of DOM fragments (from `scope_selector`) or structured
+/// sub-values (from `scope_path`). Each scope yields an object of
+/// `child.alias -> value`; with `expand_array` a repeated scope produces an
+/// array of such objects, otherwise the first scope is used.
+fn extract_nested(
+    document: &Html,
+    spec: &ExtractSpec,
+    jsonld_data: &HashMap<String, Value>,
+    microdata: &HashMap<String, Value>,
+    og_data: &HashMap<String, String>,
+    meta_data: &HashMap<String, String>,
+    js_data: &HashMap<String, Value>,
+) -> Value {
+    let scopes = build_scopes(document, spec, jsonld_data, microdata);
+
+    let objects: Vec<Value> = scopes
+        .iter()
+        .map(|scope| {
+            let mut obj = serde_json::Map::new();
+            for child in &spec.children {
+                let value = if child.children.is_empty() {
+                    eval_child(scope, child, jsonld_data, microdata, og_data, meta_data, js_data)
+                } else if let Scope::Dom(sub) = scope {
+                    // Recurse into deeper nesting within this DOM fragment.
+                    extract_nested(sub, child, jsonld_data, microdata, og_data, meta_data, js_data)
+                } else {
+                    Value::Null
+                };
+                obj.insert(child.alias.clone(), value);
+            }
+            Value::Object(obj)
+        })
+        .collect();
+
+    if spec.expand_array {
+        Value::Array(objects)
+    } else {
+        objects.into_iter().next().unwrap_or(Value::Null)
+    }
+}
+
+/// Resolve the scopes for a nested spec.
+fn build_scopes(
+    document: &Html,
+    spec: &ExtractSpec,
+    jsonld_data: &HashMap<String, Value>,
+    microdata: &HashMap<String, Value>,
+) -> Vec<Scope> {
+    // DOM scope from a CSS selector.
+    if let Some(sel_str) = spec.scope_selector.as_deref().or(spec.selector.as_deref()) {
+        if let Ok(selector) = Selector::parse(sel_str) {
+            return document
+                .select(&selector)
+                .map(|el| Scope::Dom(Html::parse_fragment(&el.html())))
+                .collect();
+        }
+        return vec![];
+    }
+
+    // Structured-data scope from a scope_path.
+    if !spec.scope_path.is_empty() {
+        let data = match spec.source.as_str() {
+            "microdata" => microdata,
+            _ => jsonld_data,
+        };
+        let type_name = &spec.scope_path[0];
+        if let Some(arr) = data.get(type_name).and_then(|v| v.as_array()) {
+            // Each item of the type becomes a scope; navigate any remaining path.
+            return arr
+                .iter()
+                .filter_map(|item| {
+                    let mut current = item;
+                    for seg in spec.scope_path.iter().skip(1) {
+                        current = current.get(seg)?;
+                    }
+                    Some(Scope::Json(current.clone()))
+                })
+                .collect();
+        }
+        return vec![];
+    }
+
+    // No narrowing: a single scope holding the whole document.
+    vec![Scope::Dom(Html::parse_fragment(&document.html()))]
+}
+
+/// Evaluate a leaf child spec within a scope.
+fn eval_child(
+    scope: &Scope,
+    child: &ExtractSpec,
+    jsonld_data: &HashMap<String, Value>,
+    microdata: &HashMap<String, Value>,
+    og_data: &HashMap<String, String>,
+    meta_data: &HashMap<String, String>,
+    js_data: &HashMap<String, Value>,
+) -> Value {
+    match scope {
+        Scope::Dom(sub) => {
+            if child.source == "css" {
+                extract_from_css(sub, child.selector.as_deref(), child.accessor.as_deref())
+                    .map(Value::String)
+                    .unwrap_or(Value::Null)
+            } else {
+                // Non-CSS children fall back to the global structured data.
+                extract_single(sub, child, jsonld_data, microdata, og_data, meta_data, js_data)
+                    .map(Value::String)
+                    .unwrap_or(Value::Null)
+            }
+        }
+        Scope::Json(value) => {
+            let mut current = value;
+            for seg in &child.path {
+                match current.get(seg) {
+                    Some(v) => current = v,
+                    None => return Value::Null,
+                }
+            }
+            current.clone()
+        }
+    }
+}
+
 /// Extract a single value based on spec
 fn extract_single(
     document: &Html,
@@ -255,7 +569,9 @@ pub fn extract_jsonld_objects(document: &Html) -> HashMap<String, Value> {
     for element in document.select(&selector) {
         let text = element.text().collect::<String>();
         if let Ok(json) = serde_json::from_str::<Value>(&text) {
-            collect_typed_objects(&json, &mut collected);
+            // Expand terms via @context and inline @id references before collecting.
+            let expanded = expand_jsonld_document(&json);
+            collect_typed_objects(&expanded, &mut collected);
         }
     }
 
@@ -266,6 +582,184 @@ pub fn extract_jsonld_objects(document: &Html) -> HashMap<String, Value> {
         .collect()
 }
 
+/// Maximum depth when inlining `@id` references, guarding against runaway graphs.
+const JSONLD_MAX_EMBED_DEPTH: usize = 6;
+
+/// Expand a JSON-LD document so downstream collection sees normalized keys and
+/// inlined nodes.
+///
+/// Reads the document's `@context` (object, or array of inline contexts; remote
+/// URL contexts are skipped) to build a term→short-name map, rewrites every
+/// property key and `@type` value to its normalized short name, then inlines any
+/// bare `{"@id": "..."}` reference with the referenced node from `@graph`.
+fn expand_jsonld_document(doc: &Value) -> Value {
+    let ctx = build_context_map(doc.get("@context"));
+    let rewritten = rewrite_terms(doc, &ctx);
+
+    let mut index: HashMap<String, Value> = HashMap::new();
+    index_nodes_by_id(&rewritten, &mut index);
+
+    embed_refs(&rewritten, &index, &mut std::collections::HashSet::new(), 0)
+}
+
+/// Build an alias→short-name map from a `@context` value.
+fn build_context_map(context: Option<&Value>) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    match context {
+        Some(Value::Object(obj)) => merge_context_object(obj, &mut map),
+        Some(Value::Array(arr)) => {
+            for item in arr {
+                if let Value::Object(obj) = item {
+                    merge_context_object(obj, &mut map);
+                }
+                // String entries are remote contexts; skip them.
+            }
+        }
+        _ => {}
+    }
+    map
+}
+
+/// Merge one inline `@context` object into the term map.
+fn merge_context_object(obj: &serde_json::Map<String, Value>, map: &mut HashMap<String, String>) {
+    for (term, value) in obj {
+        if term.starts_with('@') {
+            continue; // @vocab, @base, etc. are handled by normalization.
+        }
+        let iri = match value {
+            Value::String(s) => s.clone(),
+            Value::Object(o) => match o.get("@id") {
+                Some(Value::String(s)) => s.clone(),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        map.insert(term.clone(), normalize_iri(&iri));
+    }
+}
+
+/// Normalize an IRI/term to a short name (strip schema.org prefixes, take the
+/// final path/fragment segment).
+fn normalize_iri(iri: &str) -> String {
+    let stripped = iri
+        .strip_prefix("https://schema.org/")
+        .or_else(|| iri.strip_prefix("http://schema.org/"))
+        .or_else(|| iri.strip_prefix("schema:"))
+        .unwrap_or(iri);
+
+    stripped
+        .rsplit(|c| c == '/' || c == '#')
+        .next()
+        .unwrap_or(stripped)
+        .to_string()
+}
+
+/// Rewrite property keys and `@type` values to their normalized short names.
+fn rewrite_terms(value: &Value, ctx: &HashMap<String, String>) -> Value {
+    match value {
+        Value::Object(obj) => {
+            let mut out = serde_json::Map::new();
+            for (key, val) in obj {
+                if key == "@context" {
+                    continue; // Already consumed.
+                }
+                if key == "@type" {
+                    out.insert(key.clone(), rewrite_type(val, ctx));
+                    continue;
+                }
+                if key.starts_with('@') {
+                    out.insert(key.clone(), rewrite_terms(val, ctx));
+                    continue;
+                }
+                let new_key = ctx
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| normalize_iri(key));
+                out.insert(new_key, rewrite_terms(val, ctx));
+            }
+            Value::Object(out)
+        }
+        Value::Array(arr) => Value::Array(arr.iter().map(|v| rewrite_terms(v, ctx)).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Rewrite a `@type` value (string or array) to normalized short names.
+fn rewrite_type(value: &Value, ctx: &HashMap<String, String>) -> Value {
+    let normalize = |s: &str| ctx.get(s).cloned().unwrap_or_else(|| normalize_iri(s));
+    match value {
+        Value::String(s) => Value::String(normalize(s)),
+        Value::Array(arr) => Value::Array(
+            arr.iter()
+                .map(|v| match v {
+                    Value::String(s) => Value::String(normalize(s)),
+                    other => other.clone(),
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Index every node carrying an `@id` (including inside `@graph`).
+fn index_nodes_by_id(value: &Value, index: &mut HashMap<String, Value>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(id)) = obj.get("@id") {
+                if obj.len() > 1 {
+                    index.insert(id.clone(), value.clone());
+                }
+            }
+            for v in obj.values() {
+                index_nodes_by_id(v, index);
+            }
+        }
+        Value::Array(arr) => {
+            for v in arr {
+                index_nodes_by_id(v, index);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace bare `{"@id": "..."}` references with the referenced node.
+fn embed_refs(
+    value: &Value,
+    index: &HashMap<String, Value>,
+    visited: &mut std::collections::HashSet<String>,
+    depth: usize,
+) -> Value {
+    match value {
+        Value::Object(obj) => {
+            // A bare reference is an object whose only key is `@id`.
+            if obj.len() == 1 {
+                if let Some(Value::String(id)) = obj.get("@id") {
+                    if depth < JSONLD_MAX_EMBED_DEPTH && !visited.contains(id) {
+                        if let Some(node) = index.get(id) {
+                            visited.insert(id.clone());
+                            let embedded = embed_refs(node, index, visited, depth + 1);
+                            visited.remove(id);
+                            return embedded;
+                        }
+                    }
+                    return value.clone();
+                }
+            }
+
+            let mut out = serde_json::Map::new();
+            for (k, v) in obj {
+                out.insert(k.clone(), embed_refs(v, index, visited, depth));
+            }
+            Value::Object(out)
+        }
+        Value::Array(arr) => {
+            Value::Array(arr.iter().map(|v| embed_refs(v, index, visited, depth)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
 /// Recursively collect objects with @type, including from @graph
 /// Each type maps to an array of items (even if only one item)
 fn collect_typed_objects(value: &Value, result: &mut HashMap<String, Vec<Value>>) {
@@ -310,8 +804,75 @@ fn collect_typed_objects(value: &Value, result: &mut HashMap<String, Vec<Value>>
     }
 }
 
-/// Navigate JSON-LD data by path
-/// Data values are arrays, so we get the first item of the type's array
+/// How the first path segment selects among the entities of a `@type`.
+enum TypeSelector {
+    /// `Type` — the first entity (default).
+    First,
+    /// `Type[N]` — the Nth entity.
+    Index(usize),
+    /// `Type[*]` — every entity (expanded into rows).
+    All,
+    /// `Type{field=value}` — the entity whose `field` equals `value`.
+    Filter(String, String),
+}
+
+/// Parse a type segment into its name and selector
+/// (`Product`, `Product[2]`, `Product[*]`, `Product{sku=ABC}`).
+fn parse_type_selector(segment: &str) -> (String, TypeSelector) {
+    if let Some(open) = segment.find('[') {
+        let name = segment[..open].to_string();
+        let inner = segment[open + 1..]
+            .split(']')
+            .next()
+            .unwrap_or("")
+            .trim();
+        let sel = if inner == "*" {
+            TypeSelector::All
+        } else if let Ok(i) = inner.parse::<usize>() {
+            TypeSelector::Index(i)
+        } else {
+            TypeSelector::First
+        };
+        return (name, sel);
+    }
+
+    if let Some(open) = segment.find('{') {
+        let name = segment[..open].to_string();
+        let inner = segment[open + 1..].split('}').next().unwrap_or("");
+        if let Some(eq) = inner.find('=') {
+            let field = inner[..eq].trim().to_string();
+            let value = inner[eq + 1..].trim().to_string();
+            return (name, TypeSelector::Filter(field, value));
+        }
+    }
+
+    (segment.to_string(), TypeSelector::First)
+}
+
+/// Pick the matching entities of a type's array for a selector.
+fn select_typed<'a>(arr: &'a [Value], sel: &TypeSelector) -> Vec<&'a Value> {
+    match sel {
+        TypeSelector::First => arr.first().into_iter().collect(),
+        TypeSelector::Index(i) => arr.get(*i).into_iter().collect(),
+        TypeSelector::All => arr.iter().collect(),
+        TypeSelector::Filter(field, value) => arr
+            .iter()
+            .filter(|item| {
+                item.get(field)
+                    .map(|v| match v {
+                        Value::String(s) => s == value,
+                        other => other.to_string().trim_matches('"') == value,
+                    })
+                    .unwrap_or(false)
+            })
+            .collect(),
+    }
+}
+
+/// Navigate JSON-LD data by path.
+///
+/// The first segment selects the `@type` and optionally an entity
+/// (`Product[2]`, `Product{sku=ABC}`); remaining segments navigate into it.
 fn extract_from_jsonld(
     data: &HashMap<String, Value>,
     path: &[String],
@@ -321,12 +882,9 @@ fn extract_from_jsonld(
         return None;
     }
 
-    // First segment is the @type
-    let type_name = &path[0];
-    let arr = data.get(type_name)?;
-
-    // Get first item from array (values are always arrays now)
-    let obj = arr.as_array()?.first()?;
+    let (type_name, sel) = parse_type_selector(&path[0]);
+    let arr = data.get(&type_name)?.as_array()?;
+    let obj = *select_typed(arr, &sel).first()?;
 
     // Navigate remaining path
     let mut current = obj;
@@ -337,6 +895,32 @@ fn extract_from_jsonld(
     value_to_string(current, return_text)
 }
 
+/// Expand every entity of a `Type[*]` path into one string per match.
+fn extract_typed_expanded(
+    data: &HashMap<String, Value>,
+    path: &[String],
+    return_text: bool,
+) -> Vec<String> {
+    if path.is_empty() {
+        return vec![];
+    }
+    let (type_name, _) = parse_type_selector(&path[0]);
+    let arr = match data.get(&type_name).and_then(|v| v.as_array()) {
+        Some(a) => a,
+        None => return vec![],
+    };
+
+    arr.iter()
+        .filter_map(|item| {
+            let mut current = item;
+            for segment in path.iter().skip(1) {
+                current = current.get(segment)?;
+            }
+            value_to_string(current, return_text)
+        })
+        .collect()
+}
+
 /// Extract microdata from HTML, keyed by itemtype
 /// Returns HashMap where each value is a JSON array of items with that type
 pub fn extract_microdata(document: &Html) -> HashMap<String, Value> {
@@ -382,8 +966,10 @@ pub fn extract_microdata(document: &Html) -> HashMap<String, Value> {
         .collect()
 }
 
-/// Navigate microdata by path
-/// Data values are arrays, so we get the first item of the type's array
+/// Navigate microdata by path.
+///
+/// As with [`extract_from_jsonld`], the first segment selects the item type and
+/// optionally a specific entity (`Product[2]`, `Product{sku=ABC}`).
 fn extract_from_microdata(
     data: &HashMap<String, Value>,
     path: &[String],
@@ -393,11 +979,9 @@ fn extract_from_microdata(
         return None;
     }
 
-    let type_name = &path[0];
-    let arr = data.get(type_name)?;
-
-    // Get first item from array (values are always arrays now)
-    let obj = arr.as_array()?.first()?;
+    let (type_name, sel) = parse_type_selector(&path[0]);
+    let arr = data.get(&type_name)?.as_array()?;
+    let obj = *select_typed(arr, &sel).first()?;
 
     let mut current = obj;
     for segment in path.iter().skip(1) {
@@ -646,7 +1230,7 @@ pub fn extract_path(html: &str, path: &str) -> Option<serde_json::Value> {
     let document = Html::parse_document(html);
 
     // Parse the path syntax
-    let (css_selector, attr_name, expand_array, json_path) = parse_path_syntax(path)?;
+    let (css_selector, attr_name, plural, segments) = parse_path_syntax(path)?;
 
     // Check if this is a JS variable reference (@$varname)
     let is_js_var = attr_name.starts_with('$');
@@ -670,7 +1254,7 @@ pub fn extract_path(html: &str, path: &str) -> Option<serde_json::Value> {
             let script_text = element.text().collect::<String>();
             if let Some(vars) = parse_js_and_extract_vars(&script_text) {
                 if let Some(json_val) = vars.get(var_name) {
-                    return apply_json_path(json_val.clone(), expand_array, &json_path);
+                    return apply_json_path(json_val.clone(), plural, &segments);
                 }
             }
         }
@@ -690,55 +1274,91 @@ pub fn extract_path(html: &str, path: &str) -> Option<serde_json::Value> {
     };
 
     // If no JSON path and no array expansion, return raw string
-    if json_path.is_empty() && !expand_array {
+    if segments.is_empty() && !plural {
         return Some(serde_json::Value::String(raw_value));
     }
 
     // Parse as JSON
     let json_val: serde_json::Value = serde_json::from_str(&raw_value).ok()?;
 
-    apply_json_path(json_val, expand_array, &json_path)
+    apply_json_path(json_val, plural, &segments)
 }
 
-/// Apply JSON path and array expansion to a value
-fn apply_json_path(json_val: serde_json::Value, expand_array: bool, json_path: &[String]) -> Option<serde_json::Value> {
-    // Handle array expansion
-    if expand_array {
-        let arr = json_val.as_array()?;
+/// A single JSONPath-style navigation segment.
+enum PathSeg {
+    /// A named object key (or numeric object key used as a string).
+    Key(String),
+    /// A single array index; negative values count from the end.
+    Index(i64),
+    /// `*` — every member of an object or element of an array.
+    Wildcard,
+    /// `..name` — every value stored under `name` anywhere in the subtree.
+    Descendant(String),
+    /// `[start:end:step]` with Python semantics; `None` bounds take defaults.
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    /// `[?(...)]` — keep array elements matching the predicate. The predicate
+    /// is one or more `@.field op literal` terms combined with `&&`/`||`,
+    /// stored in disjunctive normal form (an OR of AND groups).
+    Filter(Vec<Vec<FilterTerm>>),
+}
 
-        if json_path.is_empty() {
-            return Some(serde_json::Value::Array(arr.clone()));
-        }
+/// Comparison operators accepted inside a `[?(...)]` filter predicate.
+#[derive(Clone, Copy)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
 
-        // Extract field from each element
-        let extracted: Vec<serde_json::Value> = arr
-            .iter()
-            .filter_map(|item| navigate_json(item, json_path))
-            .collect();
+/// A single `@.field op literal` comparison inside a `[?(...)]` predicate.
+struct FilterTerm {
+    field_segments: Vec<PathSeg>,
+    op: CmpOp,
+    literal: Value,
+}
 
-        return Some(serde_json::Value::Array(extracted));
+/// Collapse the navigator's result set into a single value.
+///
+/// Plural paths (those containing a wildcard, recursive descent, slice or filter)
+/// always produce a `Value::Array`; scalar paths collapse a single match to
+/// that value and `None` when nothing matched.
+fn apply_json_path(
+    json_val: serde_json::Value,
+    plural: bool,
+    segments: &[PathSeg],
+) -> Option<serde_json::Value> {
+    if segments.is_empty() {
+        return Some(json_val);
     }
 
-    // Navigate JSON path without array expansion
-    if json_path.is_empty() {
-        Some(json_val)
+    let results = navigate_json(&json_val, segments);
+
+    if plural {
+        Some(serde_json::Value::Array(results))
     } else {
-        navigate_json(&json_val, json_path)
+        match results.len() {
+            0 => None,
+            1 => results.into_iter().next(),
+            _ => Some(serde_json::Value::Array(results)),
+        }
     }
 }
 
-/// Parse the unified path syntax
-/// Returns: (css_selector, attr_name, expand_array, json_path_parts)
+/// Parse the unified path syntax.
+/// Returns: `(css_selector, attr_name, plural, segments)`.
 ///
 /// Handles:
-/// - `attr` -> attr_name="attr", json_path=[]
-/// - `attr.foo.bar` -> attr_name="attr", json_path=["foo", "bar"]
-/// - `attr[0]` -> attr_name="attr", json_path=["0"]
-/// - `attr[0].foo` -> attr_name="attr", json_path=["0", "foo"]
-/// - `attr[*]` -> attr_name="attr", expand_array=true, json_path=[]
-/// - `attr[*].id` -> attr_name="attr", expand_array=true, json_path=["id"]
-/// - `$var[0]` -> attr_name="$var", json_path=["0"]
-fn parse_path_syntax(path: &str) -> Option<(String, String, bool, Vec<String>)> {
+/// - `attr` -> attr_name="attr", segments=[]
+/// - `attr.foo.bar` -> Key("foo"), Key("bar")
+/// - `attr[0]` / `attr[-1]` -> Index(0) / Index(-1)
+/// - `attr[*]` / `attr.*` -> Wildcard (plural)
+/// - `attr..price` -> Descendant("price") (plural)
+/// - `attr[1:5]` / `attr[::2]` -> Slice(..) (plural)
+/// - `$var[0]` -> attr_name="$var", Index(0)
+fn parse_path_syntax(path: &str) -> Option<(String, String, bool, Vec<PathSeg>)> {
     let remaining = path.trim();
 
     // Find @ for attribute (scan backwards to handle @ in CSS selectors)
@@ -747,11 +1367,24 @@ fn parse_path_syntax(path: &str) -> Option<(String, String, bool, Vec<String>)>
     let css_selector = remaining[..at_pos].trim().to_string();
     let after_at = &remaining[at_pos + 1..];
 
-    // Parse the part after @: attr_name[index].path or attr_name[*].path
-    let mut json_path = Vec::new();
-    let mut expand_array = false;
+    // Filter predicates (`[?(@.price<20)]`) also contain `@`, so split at the
+    // last `@` outside brackets when that differs from the one found above.
+    let mut depth = 0i32;
+    let mut top_level_at = None;
+    for (i, c) in remaining.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '@' if depth == 0 => top_level_at = Some(i),
+            _ => {}
+        }
+    }
+    let (css_selector, after_at) = match top_level_at {
+        Some(pos) if pos != at_pos => (remaining[..pos].trim().to_string(), &remaining[pos + 1..]),
+        _ => (css_selector, after_at),
+    };
 
-    // Find the attr_name (up to first [ or .)
+    // The attr_name runs up to the first segment delimiter.
     let attr_end = after_at.find(|c| c == '[' || c == '.').unwrap_or(after_at.len());
     let attr_name = after_at[..attr_end].to_string();
 
@@ -759,85 +1392,349 @@ fn parse_path_syntax(path: &str) -> Option<(String, String, bool, Vec<String>)>
         return None;
     }
 
-    // Parse remaining path after attr_name
-    let mut rest = &after_at[attr_end..];
+    let segments = parse_segments(&after_at[attr_end..]);
+    let plural = segments.iter().any(|s| {
+        matches!(
+            s,
+            PathSeg::Wildcard | PathSeg::Descendant(_) | PathSeg::Slice(..) | PathSeg::Filter(..)
+        )
+    });
+
+    Some((css_selector, attr_name, plural, segments))
+}
+
+/// Tokenize the portion of a path after the attribute name into segments.
+fn parse_segments(mut rest: &str) -> Vec<PathSeg> {
+    let mut segments = Vec::new();
 
     while !rest.is_empty() {
-        if rest.starts_with("[*]") {
-            expand_array = true;
-            rest = &rest[3..];
+        if let Some(after) = rest.strip_prefix("..") {
+            let end = after.find(|c| c == '.' || c == '[').unwrap_or(after.len());
+            segments.push(PathSeg::Descendant(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(|c| c == '.' || c == '[').unwrap_or(after.len());
+            let token = &after[..end];
+            if token == "*" {
+                segments.push(PathSeg::Wildcard);
+            } else if !token.is_empty() {
+                segments.push(PathSeg::Key(token.to_string()));
+            }
+            rest = &after[end..];
         } else if rest.starts_with('[') {
-            // Parse [N] index
-            if let Some(end_bracket) = rest.find(']') {
-                let idx_str = &rest[1..end_bracket];
-                json_path.push(idx_str.to_string());
-                rest = &rest[end_bracket + 1..];
+            let end = match rest.find(']') {
+                Some(e) => e,
+                None => break,
+            };
+            let inner = rest[1..end].trim();
+            rest = &rest[end + 1..];
+
+            if inner == "*" {
+                segments.push(PathSeg::Wildcard);
+            } else if inner.starts_with("?(") {
+                if let Some(filter) = parse_path_filter(inner) {
+                    segments.push(filter);
+                }
+            } else if inner.contains(':') {
+                segments.push(parse_slice(inner));
+            } else if let Ok(idx) = inner.parse::<i64>() {
+                segments.push(PathSeg::Index(idx));
             } else {
-                break;
-            }
-        } else if rest.starts_with('.') {
-            // Parse .field
-            rest = &rest[1..];
-            let field_end = rest.find(|c| c == '[' || c == '.').unwrap_or(rest.len());
-            if field_end > 0 {
-                json_path.push(rest[..field_end].to_string());
-                rest = &rest[field_end..];
+                let key = inner.trim_matches(|c| c == '\'' || c == '"');
+                segments.push(PathSeg::Key(key.to_string()));
             }
         } else {
             break;
         }
     }
 
-    Some((css_selector, attr_name, expand_array, json_path))
+    segments
 }
 
-/// Navigate a JSON value by path parts
-fn navigate_json(value: &serde_json::Value, path: &[String]) -> Option<serde_json::Value> {
-    let mut current = value.clone();
+/// Parse the `start:end:step` body of a slice; each field is optional.
+fn parse_slice(inner: &str) -> PathSeg {
+    let mut parts = inner.split(':');
+    let start = parts.next().and_then(|s| s.trim().parse::<i64>().ok());
+    let end = parts.next().and_then(|s| s.trim().parse::<i64>().ok());
+    let step = parts.next().and_then(|s| s.trim().parse::<i64>().ok());
+    PathSeg::Slice(start, end, step)
+}
 
-    for part in path {
-        current = if let Ok(idx) = part.parse::<usize>() {
-            current.get(idx)?.clone()
-        } else {
-            current.get(part)?.clone()
-        };
+/// Parse a `?(...)` predicate body into a filter segment.
+///
+/// The body is one or more `@.field op literal` terms combined with top-level
+/// `&&`/`||`; it is stored in disjunctive normal form (an OR of AND groups).
+fn parse_path_filter(inner: &str) -> Option<PathSeg> {
+    // Strip the leading `?(` and trailing `)`.
+    let body = inner.strip_prefix("?(")?.strip_suffix(')')?.trim();
+
+    let mut groups = Vec::new();
+    for or_group in body.split("||") {
+        let mut terms = Vec::new();
+        for cond in or_group.split("&&") {
+            terms.push(parse_filter_term(cond.trim())?);
+        }
+        if !terms.is_empty() {
+            groups.push(terms);
+        }
     }
 
-    Some(current)
+    if groups.is_empty() {
+        return None;
+    }
+    Some(PathSeg::Filter(groups))
 }
 
-/// Extract JavaScript variables from script tags using AST parsing
-pub fn extract_js_variables(document: &Html) -> HashMap<String, Value> {
-    let mut result = HashMap::new();
-    let selector = Selector::parse("script:not([type]), script[type='text/javascript']").unwrap();
+/// Parse a single `@.field op literal` comparison term.
+fn parse_filter_term(cond: &str) -> Option<FilterTerm> {
+    let field_expr = cond.strip_prefix("@.")?;
+
+    // Operator precedence: match the two-char operators before single-char.
+    let (op, split) = ["==", "!=", "<=", ">="]
+        .iter()
+        .find_map(|o| field_expr.find(o).map(|i| (cmp_op(o), i)))
+        .or_else(|| {
+            ["<", ">"]
+                .iter()
+                .find_map(|o| field_expr.find(o).map(|i| (cmp_op(o), i)))
+        })?;
+    let op = op?;
 
-    for element in document.select(&selector) {
-        let script_text = element.text().collect::<String>();
+    let field = field_expr[..split].trim();
+    let op_len = match op {
+        CmpOp::Eq | CmpOp::Ne | CmpOp::Le | CmpOp::Ge => 2,
+        CmpOp::Lt | CmpOp::Gt => 1,
+    };
+    let literal = parse_filter_literal(field_expr[split + op_len..].trim());
+
+    let field_segments = parse_segments(&format!(".{}", field));
+    Some(FilterTerm {
+        field_segments,
+        op,
+        literal,
+    })
+}
 
-        // Parse with SWC and extract variables
-        if let Some(vars) = parse_js_and_extract_vars(&script_text) {
-            for (name, value) in vars {
-                result.insert(name, value);
+/// Map an operator token to its [`CmpOp`].
+fn cmp_op(token: &str) -> Option<CmpOp> {
+    match token {
+        "==" => Some(CmpOp::Eq),
+        "!=" => Some(CmpOp::Ne),
+        "<=" => Some(CmpOp::Le),
+        ">=" => Some(CmpOp::Ge),
+        "<" => Some(CmpOp::Lt),
+        ">" => Some(CmpOp::Gt),
+        _ => None,
+    }
+}
+
+/// Parse a filter right-hand-side literal (string, number, bool, or null).
+fn parse_filter_literal(raw: &str) -> Value {
+    if (raw.starts_with('\'') && raw.ends_with('\'') && raw.len() >= 2)
+        || (raw.starts_with('"') && raw.ends_with('"') && raw.len() >= 2)
+    {
+        return Value::String(raw[1..raw.len() - 1].to_string());
+    }
+    match raw {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => {
+            if let Ok(i) = raw.parse::<i64>() {
+                Value::Number(i.into())
+            } else if let Ok(f) = raw.parse::<f64>() {
+                serde_json::Number::from_f64(f).map(Value::Number).unwrap_or(Value::Null)
+            } else {
+                Value::String(raw.to_string())
             }
         }
     }
-
-    result
 }
 
-/// Parse JavaScript source and extract variable declarations
-fn parse_js_and_extract_vars(source: &str) -> Option<HashMap<String, Value>> {
-    let cm: Lrc<SourceMap> = Default::default();
-    let fm = cm.new_source_file(FileName::Anon.into(), source.to_string());
+/// Evaluate a predicate (OR of AND groups) against a single array element.
+fn eval_path_filter(element: &Value, groups: &[Vec<FilterTerm>]) -> bool {
+    groups
+        .iter()
+        .any(|and_group| !and_group.is_empty() && and_group.iter().all(|term| eval_filter_term(element, term)))
+}
 
-    let lexer = Lexer::new(
-        Syntax::Es(Default::default()),
-        Default::default(),
-        StringInput::from(&*fm),
-        None,
-    );
+/// Evaluate a single comparison term against an array element.
+fn eval_filter_term(element: &Value, term: &FilterTerm) -> bool {
+    let field_val = match navigate_json(element, &term.field_segments).into_iter().next() {
+        Some(v) => v,
+        None => return false,
+    };
 
-    let mut parser = Parser::new_from(lexer);
+    match term.op {
+        CmpOp::Eq => field_val == term.literal,
+        CmpOp::Ne => field_val != term.literal,
+        CmpOp::Lt | CmpOp::Le | CmpOp::Gt | CmpOp::Ge => {
+            match (field_val.as_f64(), term.literal.as_f64()) {
+                (Some(a), Some(b)) => match term.op {
+                    CmpOp::Lt => a < b,
+                    CmpOp::Le => a <= b,
+                    CmpOp::Gt => a > b,
+                    CmpOp::Ge => a >= b,
+                    _ => unreachable!(),
+                },
+                _ => false,
+            }
+        }
+    }
+}
+
+/// Evaluate the segment list against `value`, threading a working node-set.
+///
+/// Each segment maps the current set of candidate nodes to the next: child
+/// lookups filter, while wildcards, slices and recursive descent expand.
+fn navigate_json(value: &serde_json::Value, segments: &[PathSeg]) -> Vec<serde_json::Value> {
+    let mut set = vec![value.clone()];
+
+    for seg in segments {
+        let mut next = Vec::new();
+        for node in &set {
+            match seg {
+                PathSeg::Key(key) => {
+                    if let Some(child) = node.get(key) {
+                        next.push(child.clone());
+                    } else if let Ok(idx) = key.parse::<usize>() {
+                        if let Some(child) = node.get(idx) {
+                            next.push(child.clone());
+                        }
+                    }
+                }
+                PathSeg::Index(idx) => {
+                    if let Some(arr) = node.as_array() {
+                        if let Some(child) = normalize_slice_index(*idx, arr.len()).and_then(|i| arr.get(i)) {
+                            next.push(child.clone());
+                        }
+                    }
+                }
+                PathSeg::Wildcard => match node {
+                    Value::Array(arr) => next.extend(arr.iter().cloned()),
+                    Value::Object(map) => next.extend(map.values().cloned()),
+                    _ => {}
+                },
+                PathSeg::Slice(start, end, step) => {
+                    if let Some(arr) = node.as_array() {
+                        next.extend(slice_nodes(arr, *start, *end, *step));
+                    }
+                }
+                PathSeg::Descendant(name) => collect_descendant(node, name, &mut next),
+                PathSeg::Filter(groups) => {
+                    if let Some(arr) = node.as_array() {
+                        for element in arr {
+                            if eval_path_filter(element, groups) {
+                                next.push(element.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        set = next;
+    }
+
+    set
+}
+
+/// Depth-first collect every value stored under `name` anywhere in `node`.
+fn collect_descendant(node: &Value, name: &str, out: &mut Vec<Value>) {
+    match node {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if key == name {
+                    out.push(child.clone());
+                }
+                collect_descendant(child, name, out);
+            }
+        }
+        Value::Array(arr) => {
+            for child in arr {
+                collect_descendant(child, name, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve a possibly-negative index against a collection length.
+fn normalize_slice_index(idx: i64, len: usize) -> Option<usize> {
+    let resolved = if idx < 0 { idx + len as i64 } else { idx };
+    if resolved < 0 || resolved >= len as i64 {
+        None
+    } else {
+        Some(resolved as usize)
+    }
+}
+
+/// Apply a Python-style slice to an array of nodes.
+fn slice_nodes(
+    arr: &[Value],
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+) -> Vec<Value> {
+    let len = arr.len() as i64;
+    let step = step.unwrap_or(1);
+    if step == 0 {
+        return vec![];
+    }
+
+    let norm = |v: i64| if v < 0 { v + len } else { v };
+    let mut out = Vec::new();
+
+    if step > 0 {
+        let mut i = start.map(norm).unwrap_or(0).clamp(0, len);
+        let stop = end.map(norm).unwrap_or(len).clamp(0, len);
+        while i < stop {
+            out.push(arr[i as usize].clone());
+            i += step;
+        }
+    } else {
+        let mut i = start.map(norm).unwrap_or(len - 1).clamp(-1, len - 1);
+        let stop = end.map(norm).unwrap_or(-1).clamp(-1, len - 1);
+        while i > stop {
+            out.push(arr[i as usize].clone());
+            i += step;
+        }
+    }
+
+    out
+}
+
+/// Extract JavaScript variables from script tags using AST parsing
+pub fn extract_js_variables(document: &Html) -> HashMap<String, Value> {
+    let mut result = HashMap::new();
+    let selector = Selector::parse("script:not([type]), script[type='text/javascript']").unwrap();
+
+    for element in document.select(&selector) {
+        let script_text = element.text().collect::<String>();
+
+        // Parse with SWC and extract variables
+        if let Some(vars) = parse_js_and_extract_vars(&script_text) {
+            for (name, value) in vars {
+                result.insert(name, value);
+            }
+        }
+    }
+
+    result
+}
+
+/// Parse JavaScript source and extract variable declarations
+fn parse_js_and_extract_vars(source: &str) -> Option<HashMap<String, Value>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), source.to_string());
+
+    let lexer = Lexer::new(
+        Syntax::Es(Default::default()),
+        Default::default(),
+        StringInput::from(&*fm),
+        None,
+    );
+
+    let mut parser = Parser::new_from(lexer);
 
     // Try to parse as script, ignoring errors (JS in HTML often has issues)
     let script = match parser.parse_script() {
@@ -870,20 +1767,106 @@ fn extract_vars_from_stmt(stmt: &Stmt, result: &mut HashMap<String, Value>) {
             }
         }
         Stmt::Expr(expr_stmt) => {
-            // Handle: varName = value (assignment expressions)
             if let Expr::Assign(assign) = &*expr_stmt.expr {
-                if let AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) = &assign.left {
-                    let var_name = ident.sym.as_str().to_string();
-                    if let Some(value) = expr_to_json(&assign.right) {
-                        result.insert(var_name, value);
+                match &assign.left {
+                    // Bare assignment: `varName = value`.
+                    AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) => {
+                        let var_name = ident.sym.as_str().to_string();
+                        if let Some(value) = expr_to_json(&assign.right) {
+                            result.insert(var_name, value);
+                        }
+                    }
+                    // Member assignment: `window.__NEXT_DATA__ = value`, `app.data = value`.
+                    AssignTarget::Simple(SimpleAssignTarget::Member(member)) => {
+                        if let Some(key) = member_target_key(member) {
+                            if let Some(value) = expr_to_json(&assign.right) {
+                                result.insert(key, value);
+                            }
+                        }
                     }
+                    _ => {}
                 }
             }
+
+            // Descend into IIFE bodies: `(function(){ ... })()`.
+            for inner in iife_body(&expr_stmt.expr) {
+                extract_vars_from_stmt(inner, result);
+            }
+        }
+        // Recurse into wrapping blocks and conditionals.
+        Stmt::Block(block) => {
+            for inner in &block.stmts {
+                extract_vars_from_stmt(inner, result);
+            }
+        }
+        Stmt::If(if_stmt) => {
+            extract_vars_from_stmt(&if_stmt.cons, result);
+            if let Some(alt) = &if_stmt.alt {
+                extract_vars_from_stmt(alt, result);
+            }
         }
         _ => {}
     }
 }
 
+/// Build a dotted key from a member-expression assignment target.
+///
+/// A leading `window`/`self`/`globalThis`/`this` receiver is dropped, so
+/// `window.__NEXT_DATA__` stores under `__NEXT_DATA__` while deeper chains such
+/// as `app.data.config` are preserved verbatim.
+fn member_target_key(member: &MemberExpr) -> Option<String> {
+    let prop = match &member.prop {
+        MemberProp::Ident(p) => p.sym.as_str().to_string(),
+        _ => return None,
+    };
+    let base = match &*member.obj {
+        Expr::Ident(ident) => ident.sym.as_str().to_string(),
+        Expr::Member(inner) => member_target_key(inner)?,
+        Expr::This(_) => "this".to_string(),
+        _ => return None,
+    };
+
+    let key = format!("{}.{}", base, prop);
+    // Strip a global receiver from the head of the chain.
+    if let Some((head, tail)) = key.split_once('.') {
+        if matches!(head, "window" | "self" | "globalThis" | "this") {
+            return Some(tail.to_string());
+        }
+    }
+    Some(key)
+}
+
+/// If `expr` is an immediately-invoked function expression, return its body
+/// statements so assignments inside the closure can be discovered.
+fn iife_body(expr: &Expr) -> &[Stmt] {
+    let call = match expr {
+        Expr::Call(call) => call,
+        Expr::Paren(paren) => return iife_body(&paren.expr),
+        _ => return &[],
+    };
+
+    if let Callee::Expr(callee) = &call.callee {
+        let callee = match &**callee {
+            Expr::Paren(paren) => &*paren.expr,
+            other => other,
+        };
+        match callee {
+            Expr::Fn(fn_expr) => {
+                if let Some(body) = &fn_expr.function.body {
+                    return &body.stmts;
+                }
+            }
+            Expr::Arrow(arrow) => {
+                if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+                    return &body.stmts;
+                }
+            }
+            _ => {}
+        }
+    }
+    &[]
+}
+
 /// Convert a JavaScript expression to a JSON Value
 fn expr_to_json(expr: &Expr) -> Option<Value> {
     match expr {
@@ -995,105 +1978,829 @@ fn is_json_parse_call(call: &CallExpr) -> bool {
             }
         }
     }
-    false
-}
+    false
+}
+
+/// Convert property name to string
+fn prop_name_to_string(name: &PropName) -> Option<String> {
+    match name {
+        PropName::Ident(ident) => Some(ident.sym.as_str().to_string()),
+        PropName::Str(s) => s.value.as_str().map(|v| v.to_string()),
+        PropName::Num(n) => Some(n.value.to_string()),
+        _ => None,
+    }
+}
+
+/// Navigate JS variable data by path
+fn extract_from_js(
+    data: &HashMap<String, Value>,
+    path: &[String],
+    return_text: bool,
+) -> Option<String> {
+    if path.is_empty() {
+        return None;
+    }
+
+    // Thin wrapper over the string-based query engine: rebuild the path as a
+    // dotted/indexed query and navigate with the shared parser.
+    let query = keys_to_query(path);
+    let (head, rest) = split_first_segment(&query);
+    let root = data.get(&head)?;
+
+    let segments = parse_segments(&rest);
+    let value = if segments.is_empty() {
+        root.clone()
+    } else {
+        navigate_json(root, &segments).into_iter().next()?
+    };
+
+    value_to_string(&value, return_text)
+}
+
+/// Query embedded JSON variables with a JSONPath-like string.
+///
+/// The first segment names a top-level variable; the remainder is parsed by the
+/// shared navigator, so `items[0].url`, `results[*].href` and recursive descent
+/// (`meta..company_name`) all work. Every matching *scalar* is returned as a
+/// string; objects, arrays and nulls are skipped.
+pub fn query_json(data: &HashMap<String, Value>, query: &str) -> Vec<String> {
+    let (head, rest) = split_first_segment(query);
+    let root = match data.get(&head) {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let segments = parse_segments(&rest);
+    let matches = if segments.is_empty() {
+        vec![root.clone()]
+    } else {
+        navigate_json(root, &segments)
+    };
+
+    matches.iter().filter_map(scalar_to_string).collect()
+}
+
+/// Split a query into its leading key and the remaining `.`/`[` segments.
+fn split_first_segment(query: &str) -> (String, String) {
+    let end = query.find(|c| c == '.' || c == '[').unwrap_or(query.len());
+    (query[..end].to_string(), query[end..].to_string())
+}
+
+/// Rebuild a key array into query syntax (`["jobs","0","title"]` -> `jobs[0].title`).
+fn keys_to_query(path: &[String]) -> String {
+    let mut query = String::new();
+    for (i, key) in path.iter().enumerate() {
+        if i == 0 {
+            query.push_str(key);
+        } else if key.parse::<usize>().is_ok() {
+            query.push('[');
+            query.push_str(key);
+            query.push(']');
+        } else {
+            query.push('.');
+            query.push_str(key);
+        }
+    }
+    query
+}
+
+/// Render a scalar JSON value as a string; non-scalars yield `None`.
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+/// Convert JSON value to string
+fn value_to_string(value: &Value, return_text: bool) -> Option<String> {
+    if return_text {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            _ => Some(value.to_string()),
+        }
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Domain-based filtering for [`extract_links_filtered`].
+///
+/// The allow/deny domain matches are suffix-aware, so
+/// `deny_domains = ["doubleclick.net"]` also removes `ad.doubleclick.net`;
+/// `same_origin_only` compares hosts exactly.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LinkFilter {
+    /// When non-empty, only links on these domains are kept.
+    #[serde(default)]
+    pub allow_domains: Vec<String>,
+    /// Links on these domains are dropped.
+    #[serde(default)]
+    pub deny_domains: Vec<String>,
+    /// Keep only links sharing the base URL's host.
+    #[serde(default)]
+    pub same_origin_only: bool,
+}
+
+impl LinkFilter {
+    /// Whether a link `host` passes the filter given the base URL's host.
+    fn allows(&self, host: &str, base_host: &str) -> bool {
+        if self.same_origin_only && host != base_host {
+            return false;
+        }
+        if !self.allow_domains.is_empty()
+            && !self.allow_domains.iter().any(|d| domain_suffix_match(host, d))
+        {
+            return false;
+        }
+        if self.deny_domains.iter().any(|d| domain_suffix_match(host, d)) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Suffix-aware host match: `host == domain` or `host` ends with `.domain`.
+fn domain_suffix_match(host: &str, domain: &str) -> bool {
+    let host = host.to_lowercase();
+    let domain = domain.trim_start_matches('.').to_lowercase();
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+/// Extract links from HTML using a CSS selector
+/// Returns a list of absolute URLs
+pub fn extract_links(html: &str, selector: &str, base_url: &str) -> Vec<String> {
+    extract_links_filtered(html, selector, base_url, &LinkFilter::default())
+}
+
+/// Extract links, keeping only those that pass `filter`.
+///
+/// Behaves like [`extract_links`] — absolutizing against `base_url` and
+/// dropping `javascript:`/`mailto:`/`tel:`/`#anchor` hrefs — then applies the
+/// domain allow/deny/same-origin rules in [`LinkFilter`].
+pub fn extract_links_filtered(
+    html: &str,
+    selector: &str,
+    base_url: &str,
+    filter: &LinkFilter,
+) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let mut links = Vec::new();
+
+    // Parse the base URL for resolving relative links
+    let base = match url::Url::parse(base_url) {
+        Ok(u) => u,
+        Err(_) => return links,
+    };
+    let base_host = base.host_str().unwrap_or("").to_lowercase();
+
+    // Parse selector - default to 'a[href]' if empty
+    let sel_str = if selector.is_empty() { "a[href]" } else { selector };
+    let sel = match Selector::parse(sel_str) {
+        Ok(s) => s,
+        Err(_) => return links,
+    };
+
+    for element in document.select(&sel) {
+        // Get href attribute
+        if let Some(href) = element.value().attr("href") {
+            // Skip empty, javascript:, mailto:, tel:, and anchor links
+            let href_trimmed = href.trim();
+            if href_trimmed.is_empty()
+                || href_trimmed.starts_with("javascript:")
+                || href_trimmed.starts_with("mailto:")
+                || href_trimmed.starts_with("tel:")
+                || href_trimmed.starts_with('#')
+            {
+                continue;
+            }
+
+            // Resolve relative URL
+            match base.join(href_trimmed) {
+                Ok(absolute_url) => {
+                    // Only include http/https URLs
+                    if absolute_url.scheme() == "http" || absolute_url.scheme() == "https" {
+                        let host = absolute_url.host_str().unwrap_or("").to_lowercase();
+                        if filter.allows(&host, &base_host) {
+                            links.push(absolute_url.to_string());
+                        }
+                    }
+                }
+                Err(_) => continue,
+            }
+        }
+    }
+
+    links
+}
+
+/// URL schemes recognized by [`autolink`].
+const AUTOLINK_SCHEMES: &[&str] = &["https", "http", "ftp", "mailto"];
+
+/// Wrap bare URLs in plain text with anchor tags.
+///
+/// Recognizes the schemes in [`AUTOLINK_SCHEMES`] plus `www.`-prefixed hosts,
+/// stopping each match at whitespace, `<`, quotes or a non-breaking space.
+/// Extra `attrs` (e.g. `("rel", "nofollow")`) are added to every generated
+/// anchor. Existing markup — and the text inside existing `<a>` elements — is
+/// left untouched.
+pub fn autolink(text: &str, attrs: &[(&str, &str)]) -> String {
+    let tag_re = regex::Regex::new(r"<[^>]+>").unwrap();
+    let mut out = String::new();
+    let mut last = 0;
+    let mut anchor_depth = 0u32;
+
+    for tag in tag_re.find_iter(text) {
+        let between = &text[last..tag.start()];
+        if anchor_depth > 0 {
+            out.push_str(between);
+        } else {
+            out.push_str(&linkify_text(between, attrs));
+        }
+
+        out.push_str(tag.as_str());
+        let lower = tag.as_str().to_lowercase();
+        if lower.starts_with("<a ") || lower == "<a>" || lower.starts_with("<a\t") {
+            anchor_depth += 1;
+        } else if lower.starts_with("</a") {
+            anchor_depth = anchor_depth.saturating_sub(1);
+        }
+        last = tag.end();
+    }
+
+    let tail = &text[last..];
+    if anchor_depth > 0 {
+        out.push_str(tail);
+    } else {
+        out.push_str(&linkify_text(tail, attrs));
+    }
+    out
+}
+
+/// Wrap bare URLs in a tag-free text run.
+fn linkify_text(segment: &str, attrs: &[(&str, &str)]) -> String {
+    // Build the scheme alternation once per call (cheap relative to fetching).
+    let scheme_alt: Vec<&str> = AUTOLINK_SCHEMES
+        .iter()
+        .filter(|s| **s != "mailto")
+        .copied()
+        .collect();
+    let pattern = format!(
+        r#"(?i)((?:{})://[^\s<>"'\x{{00A0}}]+|mailto:[^\s<>"'\x{{00A0}}]+|\bwww\.[^\s<>"'\x{{00A0}}]+)"#,
+        scheme_alt.join("|")
+    );
+    let url_re = match regex::Regex::new(&pattern) {
+        Ok(re) => re,
+        Err(_) => return segment.to_string(),
+    };
+
+    let attr_str: String = attrs
+        .iter()
+        .map(|(k, v)| format!(" {}=\"{}\"", k, v))
+        .collect();
+
+    let mut out = String::new();
+    let mut last = 0;
+    for m in url_re.find_iter(segment) {
+        out.push_str(&segment[last..m.start()]);
+        let (matched, trailing) = trim_trailing_punctuation(m.as_str());
+        let href = if matched.to_lowercase().starts_with("www.") {
+            format!("http://{}", matched)
+        } else {
+            matched.to_string()
+        };
+        out.push_str(&format!("<a href=\"{}\"{}>{}</a>", href, attr_str, matched));
+        out.push_str(trailing);
+        last = m.end();
+    }
+    out.push_str(&segment[last..]);
+    out
+}
+
+/// Split trailing sentence punctuation off the end of a matched URL.
+fn trim_trailing_punctuation(url: &str) -> (&str, &str) {
+    let mut end = url.len();
+    for (idx, ch) in url.char_indices().rev() {
+        match ch {
+            '.' | ',' | '!' | '?' | ':' | ';' | '"' | '\'' => end = idx,
+            ')' | ']' | '}' if !url[..idx].contains(matching_open(ch)) => end = idx,
+            _ => break,
+        }
+    }
+    (&url[..end], &url[end..])
+}
+
+/// The opening bracket matching a closing bracket.
+fn matching_open(close: char) -> char {
+    match close {
+        ')' => '(',
+        ']' => '[',
+        '}' => '{',
+        other => other,
+    }
+}
+
+/// The cleaned main content of a page, as produced by [`extract_readable`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadableArticle {
+    /// Document title, from `<title>` or `og:title`.
+    pub title: Option<String>,
+    /// Author byline, if one could be found.
+    pub byline: Option<String>,
+    /// Cleaned article HTML with links/images resolved against the base URL.
+    pub content: String,
+}
+
+/// Extract the primary article body from a cluttered page.
+///
+/// Block elements are scored in the style of Mozilla Readability: every
+/// `<p>`/`<div>`/`<td>`/`<pre>` gets a base point plus a point per comma and one
+/// per ~100 characters (capped at 3), scaled down by its link density; each
+/// node's score flows fully to its parent and half to its grandparent. The
+/// top-scoring node and its high-scoring siblings are kept, boilerplate is
+/// stripped, and relative `src`/`href` values are resolved against `base_url`.
+pub fn extract_readable(html: &str, base_url: &str) -> ReadableArticle {
+    let document = Html::parse_document(html);
+    let base = url::Url::parse(base_url).ok();
+
+    let title = readable_title(&document);
+    let byline = readable_byline(&document);
+
+    // Score every block candidate, propagating to ancestors.
+    let mut scores: HashMap<ego_tree::NodeId, f64> = HashMap::new();
+    for node in document.tree.nodes() {
+        let name = match node.value().as_element() {
+            Some(el) => el.name(),
+            None => continue,
+        };
+        if !matches!(name, "p" | "div" | "td" | "pre") {
+            continue;
+        }
+
+        let text = node_text(node);
+        let text_len = text.chars().count();
+        if text_len < 25 {
+            continue;
+        }
+
+        let mut score = 1.0;
+        score += text.matches(',').count() as f64;
+        score += (text_len as f64 / 100.0).min(3.0);
+        score *= 1.0 - link_density(node);
+
+        if let Some(parent) = node.parent() {
+            *scores.entry(parent.id()).or_insert(0.0) += score;
+            if let Some(grandparent) = parent.parent() {
+                *scores.entry(grandparent.id()).or_insert(0.0) += score / 2.0;
+            }
+        }
+    }
+
+    let top = scores
+        .iter()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(id, score)| (*id, *score));
+
+    let mut content = String::new();
+    if let Some((top_id, top_score)) = top {
+        let threshold = (top_score * 0.2).max(10.0);
+        let top_node = document.tree.get(top_id).unwrap();
+
+        // Keep the top candidate plus its qualifying siblings.
+        if let Some(parent) = top_node.parent() {
+            for sibling in parent.children() {
+                if sibling.value().as_element().is_none() {
+                    continue;
+                }
+                let keep = sibling.id() == top_id
+                    || scores.get(&sibling.id()).copied().unwrap_or(0.0) >= threshold;
+                if keep {
+                    clean_serialize(sibling, base.as_ref(), &mut content);
+                }
+            }
+        } else {
+            clean_serialize(top_node, base.as_ref(), &mut content);
+        }
+    }
+
+    ReadableArticle {
+        title,
+        byline,
+        content: content.trim().to_string(),
+    }
+}
+
+/// Concatenated text of a node's descendants.
+fn node_text(node: ego_tree::NodeRef<Node>) -> String {
+    let mut out = String::new();
+    for descendant in node.descendants() {
+        if let Node::Text(text) = descendant.value() {
+            out.push_str(text);
+        }
+    }
+    out
+}
+
+/// Ratio of anchor text length to total text length for a node.
+fn link_density(node: ego_tree::NodeRef<Node>) -> f64 {
+    let total = node_text(node).chars().count();
+    if total == 0 {
+        return 0.0;
+    }
+    let mut link_len = 0;
+    for descendant in node.descendants() {
+        if let Some(el) = descendant.value().as_element() {
+            if el.name() == "a" {
+                link_len += node_text(descendant).chars().count();
+            }
+        }
+    }
+    link_len as f64 / total as f64
+}
+
+/// Whether an element is boilerplate to be stripped from readable output.
+fn is_boilerplate(el: &scraper::node::Element) -> bool {
+    if matches!(el.name(), "script" | "style" | "form" | "noscript") {
+        return true;
+    }
+    let haystack = format!(
+        "{} {}",
+        el.attr("class").unwrap_or(""),
+        el.attr("id").unwrap_or("")
+    )
+    .to_lowercase();
+    ["comment", "sidebar", "footer", "ad-"]
+        .iter()
+        .any(|needle| haystack.contains(needle))
+}
+
+/// Serialize a node to cleaned HTML, dropping boilerplate and absolutizing URLs.
+fn clean_serialize(node: ego_tree::NodeRef<Node>, base: Option<&url::Url>, out: &mut String) {
+    match node.value() {
+        Node::Text(text) => out.push_str(text),
+        Node::Element(element) => {
+            if is_boilerplate(element) {
+                return;
+            }
+            let name = element.name();
+            out.push('<');
+            out.push_str(name);
+            for (attr, value) in element.attrs() {
+                let resolved = if matches!(attr, "src" | "href") {
+                    base.and_then(|b| b.join(value).ok())
+                        .map(|u| u.to_string())
+                        .unwrap_or_else(|| value.to_string())
+                } else {
+                    value.to_string()
+                };
+                out.push(' ');
+                out.push_str(attr);
+                out.push_str("=\"");
+                out.push_str(&resolved.replace('"', "&quot;"));
+                out.push('"');
+            }
+            out.push('>');
+            for child in node.children() {
+                clean_serialize(child, base, out);
+            }
+            out.push_str("</");
+            out.push_str(name);
+            out.push('>');
+        }
+        _ => {}
+    }
+}
+
+/// Pull the document title from `<title>` or `og:title`.
+fn readable_title(document: &Html) -> Option<String> {
+    if let Ok(sel) = Selector::parse("title") {
+        if let Some(el) = document.select(&sel).next() {
+            let text = el.text().collect::<String>().trim().to_string();
+            if !text.is_empty() {
+                return Some(text);
+            }
+        }
+    }
+    extract_opengraph(document).get("title").cloned()
+}
+
+/// Find an author byline from common metadata/markup patterns.
+fn readable_byline(document: &Html) -> Option<String> {
+    if let Ok(sel) = Selector::parse(r#"meta[name="author"]"#) {
+        if let Some(content) = document.select(&sel).next().and_then(|el| el.value().attr("content")) {
+            if !content.trim().is_empty() {
+                return Some(content.trim().to_string());
+            }
+        }
+    }
+    for pattern in [r#"[rel="author"]"#, ".byline", ".author"] {
+        if let Ok(sel) = Selector::parse(pattern) {
+            if let Some(el) = document.select(&sel).next() {
+                let text = el.text().collect::<String>().trim().to_string();
+                if !text.is_empty() {
+                    return Some(text);
+                }
+            }
+        }
+    }
+    None
+}
+
+// ============================================================================
+// JSONPath engine
+// ============================================================================
+
+/// Evaluate a JSONPath expression, returning every matching value.
+///
+/// Supports `$` root, `.key`/`['key']` children, `[n]` indices (negative
+/// counts from the end), `[*]` wildcards, `..key` recursive descent,
+/// `[start:end:step]` slices and `[?(@.field <op> <literal>)]` filters. The
+/// expression is lowered onto the shared path navigator so a single
+/// segment/filter engine (see [`navigate_json`] and [`parse_path_filter`])
+/// backs both the [`extract_path`] syntax and JSONPath; the `->` arrow syntax
+/// in [`navigate_json_path`] is evaluated by its own loop.
+fn jsonpath_query(root: &Value, path: &str) -> Vec<Value> {
+    let trimmed = path.trim();
+    let rest = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let segments = parse_segments(rest);
+    navigate_json(root, &segments)
+}
+
+// ============================================================================
+// Site-specific extractors (yt-dlp style)
+// ============================================================================
+
+/// A site-specific extractor, selected by URL.
+///
+/// Implementors decide whether they apply to a URL via [`matches`](Extractor::matches)
+/// and turn a page into structured JSON via [`extract`](Extractor::extract). The
+/// [`ExtractorRegistry`] dispatches to the highest-priority matching extractor,
+/// falling back to [`GenericExtractor`] when none claim the URL.
+pub trait Extractor: Send + Sync {
+    /// Stable identifier for this extractor (used by [`ExtractorRegistry::keys`]).
+    fn key(&self) -> &str;
+    /// Whether this extractor should handle `url`.
+    fn matches(&self, url: &str) -> bool;
+    /// Produce structured JSON for `html` served from `url`.
+    fn extract(&self, html: &str, url: &str) -> Value;
+    /// Selection priority; the registry prefers the highest among matches.
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+/// Default extractor: merges JSON-LD, OpenGraph and `<meta>` data.
+///
+/// Always matches, so it doubles as the registry's fallback.
+pub struct GenericExtractor;
+
+impl Extractor for GenericExtractor {
+    fn key(&self) -> &str {
+        "generic"
+    }
+
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+
+    fn extract(&self, html: &str, _url: &str) -> Value {
+        let document = Html::parse_document(html);
+        let jsonld = extract_jsonld_objects(&document);
+        let og = extract_opengraph(&document);
+        let meta = extract_meta_tags(&document);
+
+        serde_json::json!({
+            "jsonld": jsonld,
+            "og": og,
+            "meta": meta,
+        })
+    }
+}
+
+/// Example domain-specific extractor for `example.com` product pages.
+///
+/// Knows where this (fictional) site keeps its title, price and SKU so callers
+/// don't have to hand-write selectors. Real extractors follow the same shape.
+pub struct ExampleProductExtractor;
+
+impl Extractor for ExampleProductExtractor {
+    fn key(&self) -> &str {
+        "example-product"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+            .map(|h| h == "example.com" || h.ends_with(".example.com"))
+            .unwrap_or(false)
+    }
+
+    fn extract(&self, html: &str, _url: &str) -> Value {
+        let document = Html::parse_document(html);
+        serde_json::json!({
+            "title": extract_from_css(&document, Some("h1.product-title"), Some("text")),
+            "price": extract_from_css(&document, Some(".price"), Some("text")),
+            "sku": extract_from_css(&document, Some("[itemprop=\"sku\"]"), Some("text")),
+        })
+    }
+}
+
+/// Ordered collection of extractors with a generic fallback.
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn Extractor>>,
+    fallback: Box<dyn Extractor>,
+}
+
+impl ExtractorRegistry {
+    /// Registry with the built-in site extractors registered.
+    pub fn new() -> Self {
+        Self {
+            extractors: vec![Box::new(ExampleProductExtractor)],
+            fallback: Box::new(GenericExtractor),
+        }
+    }
+
+    /// Registry seeded with config-loaded [`SiteExtractor`]s on top of the
+    /// built-in ones, so per-domain rules can be supplied as data.
+    pub fn with_site_extractors(sites: Vec<SiteExtractor>) -> Self {
+        let mut registry = Self::new();
+        for site in sites {
+            registry.register(Box::new(site));
+        }
+        registry
+    }
+
+    /// Register an additional extractor, checked before the fallback.
+    pub fn register(&mut self, extractor: Box<dyn Extractor>) {
+        self.extractors.push(extractor);
+    }
+
+    /// Keys of the registered site extractors (excluding the fallback).
+    pub fn keys(&self) -> Vec<&str> {
+        self.extractors.iter().map(|e| e.key()).collect()
+    }
+
+    /// The highest-priority extractor claiming `url`, if any.
+    pub fn find(&self, url: &str) -> Option<&dyn Extractor> {
+        self.extractors
+            .iter()
+            .filter(|e| e.matches(url))
+            .max_by_key(|e| e.priority())
+            .map(|e| e.as_ref())
+    }
+
+    /// Extract using the highest-priority matching extractor, or the fallback.
+    pub fn extract(&self, html: &str, url: &str) -> Value {
+        match self.find(url) {
+            Some(extractor) => extractor.extract(html, url),
+            None => self.fallback.extract(html, url),
+        }
+    }
+}
+
+/// A declarative, config-loadable site extractor (yt-dlp style).
+///
+/// A URL is claimed when its host matches `host_glob` (a `*.`-style glob) and
+/// its path matches `path_regex`; either may be omitted to match anything.
+/// Matching pages are run through [`extract_all`] with the extractor's named
+/// [`ExtractSpec`]s, so new sites can be added as data rather than code.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SiteExtractor {
+    /// Stable identifier for this extractor.
+    pub key: String,
+    /// Host glob (e.g. `*.example.com`); matches any host when absent.
+    #[serde(default)]
+    pub host_glob: Option<String>,
+    /// Regex tested against the URL path; matches any path when absent.
+    #[serde(default)]
+    pub path_regex: Option<String>,
+    /// Selection priority among competing extractors (higher wins).
+    #[serde(default)]
+    pub priority: i32,
+    /// The specs run through [`extract_all`] for matching pages.
+    pub specs: Vec<ExtractSpec>,
+}
+
+impl Extractor for SiteExtractor {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        let parsed = match url::Url::parse(url) {
+            Ok(u) => u,
+            Err(_) => return false,
+        };
+
+        if let Some(glob) = &self.host_glob {
+            let host = parsed.host_str().unwrap_or("").to_lowercase();
+            if !host_glob_match(glob, &host) {
+                return false;
+            }
+        }
+
+        if let Some(pattern) = &self.path_regex {
+            match regex::Regex::new(pattern) {
+                Ok(re) if re.is_match(parsed.path()) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    fn extract(&self, html: &str, _url: &str) -> Value {
+        let request = ExtractionRequest {
+            specs: self.specs.clone(),
+        };
+        let result = extract_all(html, &request);
+        extraction_result_to_value(&result)
+    }
 
-/// Convert property name to string
-fn prop_name_to_string(name: &PropName) -> Option<String> {
-    match name {
-        PropName::Ident(ident) => Some(ident.sym.as_str().to_string()),
-        PropName::Str(s) => s.value.as_str().map(|v| v.to_string()),
-        PropName::Num(n) => Some(n.value.to_string()),
-        _ => None,
+    fn priority(&self) -> i32 {
+        self.priority
     }
 }
 
-/// Navigate JS variable data by path
-fn extract_from_js(
-    data: &HashMap<String, Value>,
-    path: &[String],
-    return_text: bool,
-) -> Option<String> {
-    if path.is_empty() {
-        return None;
+/// Parse a JSON array of [`SiteExtractor`] definitions (a per-domain rule set).
+///
+/// Surfaces the deserialization error as a string so callers can report bad
+/// config rather than panicking.
+pub fn load_site_extractors(json: &str) -> Result<Vec<SiteExtractor>, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+/// Match a host against a `*.`-style glob (`*.example.com`, `example.*`).
+fn host_glob_match(glob: &str, host: &str) -> bool {
+    let glob = glob.to_lowercase();
+    if let Some(suffix) = glob.strip_prefix("*.") {
+        return host == suffix || host.ends_with(&format!(".{}", suffix));
     }
+    if let Some(prefix) = glob.strip_suffix(".*") {
+        return host == prefix || host.starts_with(&format!("{}.", prefix));
+    }
+    glob == host
+}
 
-    // First segment is the variable name
-    let var_name = &path[0];
-    let obj = data.get(var_name)?;
+/// Flatten an [`ExtractionResult`] into a JSON object keyed by alias.
+fn extraction_result_to_value(result: &ExtractionResult) -> Value {
+    let mut map = serde_json::Map::new();
 
-    // Navigate remaining path
-    let mut current = obj;
-    for segment in path.iter().skip(1) {
-        // Try as object key first, then as array index
-        current = current.get(segment).or_else(|| {
-            segment.parse::<usize>().ok().and_then(|idx| current.get(idx))
-        })?;
+    for (alias, value) in &result.values {
+        let json = match value {
+            Some(raw) => serde_json::from_str::<Value>(raw).unwrap_or_else(|_| Value::String(raw.clone())),
+            None => Value::Null,
+        };
+        map.insert(alias.clone(), json);
     }
 
-    value_to_string(current, return_text)
+    for (alias, items) in &result.expanded_values {
+        let arr = items.iter().map(|s| Value::String(s.clone())).collect();
+        map.insert(alias.clone(), Value::Array(arr));
+    }
+
+    Value::Object(map)
 }
 
-/// Convert JSON value to string
-fn value_to_string(value: &Value, return_text: bool) -> Option<String> {
-    if return_text {
-        match value {
-            Value::String(s) => Some(s.clone()),
-            Value::Null => None,
-            _ => Some(value.to_string()),
-        }
-    } else {
-        Some(value.to_string())
+impl Default for ExtractorRegistry {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-/// Extract links from HTML using a CSS selector
-/// Returns a list of absolute URLs
-pub fn extract_links(html: &str, selector: &str, base_url: &str) -> Vec<String> {
-    let document = Html::parse_document(html);
-    let mut links = Vec::new();
-
-    // Parse the base URL for resolving relative links
-    let base = match url::Url::parse(base_url) {
-        Ok(u) => u,
-        Err(_) => return links,
-    };
+/// Dispatch `html`/`url` through the default registry, returning tailored JSON.
+pub fn extract_site(html: &str, url: &str) -> Value {
+    ExtractorRegistry::new().extract(html, url)
+}
 
-    // Parse selector - default to 'a[href]' if empty
-    let sel_str = if selector.is_empty() { "a[href]" } else { selector };
-    let sel = match Selector::parse(sel_str) {
-        Ok(s) => s,
-        Err(_) => return links,
-    };
+/// Consult the registry and return site-tailored JSON, falling back to the
+/// generic JSON-LD/OpenGraph/`<meta>` extractor when no site extractor matches.
+pub fn extract_auto(html: &str, url: &str) -> Value {
+    extract_site(html, url)
+}
 
-    for element in document.select(&sel) {
-        // Get href attribute
-        if let Some(href) = element.value().attr("href") {
-            // Skip empty, javascript:, mailto:, tel:, and anchor links
-            let href_trimmed = href.trim();
-            if href_trimmed.is_empty()
-                || href_trimmed.starts_with("javascript:")
-                || href_trimmed.starts_with("mailto:")
-                || href_trimmed.starts_with("tel:")
-                || href_trimmed.starts_with('#')
-            {
-                continue;
-            }
+/// Keys of the site extractors registered in the default registry.
+pub fn list_extractors() -> Vec<String> {
+    ExtractorRegistry::new()
+        .keys()
+        .into_iter()
+        .map(|k| k.to_string())
+        .collect()
+}
 
-            // Resolve relative URL
-            match base.join(href_trimmed) {
-                Ok(absolute_url) => {
-                    // Only include http/https URLs
-                    if absolute_url.scheme() == "http" || absolute_url.scheme() == "https" {
-                        links.push(absolute_url.to_string());
-                    }
-                }
-                Err(_) => continue,
-            }
-        }
-    }
+/// Key of the highest-priority extractor that claims `url`, if any.
+pub fn find_extractor(url: &str) -> Option<String> {
+    ExtractorRegistry::new().find(url).map(|e| e.key().to_string())
+}
 
-    links
+/// Dispatch `url`/`html` through the default registry, returning tailored JSON
+/// (or the generic fallback when no site extractor matches).
+pub fn extract_for_url(url: &str, html: &str) -> Value {
+    ExtractorRegistry::new().extract(html, url)
 }
 
 #[cfg(test)]
@@ -1129,6 +2836,356 @@ mod tests {
         assert_eq!(product["offers"]["price"], "19.99");
     }
 
+    #[test]
+    fn test_nested_extraction() {
+        let html = r#"
+        <div class="product">
+            <h2 class="name">Widget</h2>
+            <div class="offer">
+                <span class="price">19.99</span>
+                <span class="currency">USD</span>
+            </div>
+        </div>
+        "#;
+
+        let spec = ExtractSpec {
+            source: "css".to_string(),
+            alias: "product".to_string(),
+            scope_selector: Some("div.product".to_string()),
+            children: vec![
+                ExtractSpec {
+                    source: "css".to_string(),
+                    selector: Some("h2.name".to_string()),
+                    accessor: Some("text".to_string()),
+                    alias: "name".to_string(),
+                    ..Default::default()
+                },
+                ExtractSpec {
+                    source: "css".to_string(),
+                    selector: Some(".price".to_string()),
+                    accessor: Some("text".to_string()),
+                    alias: "price".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = extract_all(html, &ExtractionRequest { specs: vec![spec] });
+        let raw = result.values.get("product").unwrap().clone().unwrap();
+        let value: Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(value["name"], "Widget");
+        assert_eq!(value["price"], "19.99");
+    }
+
+    #[test]
+    fn test_jsonld_graph_id_embedding() {
+        let html = r#"
+        <script type="application/ld+json">
+        {
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@id": "#product", "@type": "Product", "name": "Widget", "brand": {"@id": "#brand"}},
+                {"@id": "#brand", "@type": "Brand", "name": "Acme"}
+            ]
+        }
+        </script>
+        "#;
+
+        let document = Html::parse_document(html);
+        let jsonld = extract_jsonld_objects(&document);
+        let product = &jsonld["Product"][0];
+        assert_eq!(product["name"], "Widget");
+        // The @id reference to the Brand node is inlined.
+        assert_eq!(product["brand"]["name"], "Acme");
+    }
+
+    #[test]
+    fn test_jsonpath_filter_and_descent() {
+        let data = serde_json::json!({
+            "offers": [
+                {"sku": "A", "price": 40},
+                {"sku": "B", "price": 60},
+                {"sku": "C", "price": 20}
+            ]
+        });
+
+        let cheap = jsonpath_query(&data, "$.offers[?(@.price < 50)].sku");
+        assert_eq!(cheap, vec![serde_json::json!("A"), serde_json::json!("C")]);
+
+        let all_prices = jsonpath_query(&data, "$..price");
+        assert_eq!(all_prices.len(), 3);
+
+        let sliced = jsonpath_query(&data, "$.offers[0:2]");
+        assert_eq!(sliced.len(), 2);
+
+        let last = jsonpath_query(&data, "$.offers[-1].sku");
+        assert_eq!(last, vec![serde_json::json!("C")]);
+    }
+
+    #[test]
+    fn test_jsonpath_combined_filter() {
+        let data = serde_json::json!({
+            "offers": [
+                {"sku": "A", "price": 40, "active": true},
+                {"sku": "B", "price": 15, "active": false},
+                {"sku": "C", "price": 10, "active": true}
+            ]
+        });
+
+        // `&&` keeps only cheap *and* active offers.
+        let cheap_active = jsonpath_query(&data, "$.offers[?(@.price<50 && @.active==true)].sku");
+        assert_eq!(cheap_active, vec![serde_json::json!("A"), serde_json::json!("C")]);
+
+        // `||` keeps offers matching either branch.
+        let either = jsonpath_query(&data, "$.offers[?(@.price>30 || @.active==false)].sku");
+        assert_eq!(either, vec![serde_json::json!("A"), serde_json::json!("B")]);
+    }
+
+    #[test]
+    fn test_registry_dispatch() {
+        let html = r#"<html><body>
+            <h1 class="product-title">Widget</h1>
+            <span class="price">$9.99</span>
+        </body></html>"#;
+
+        // example.com is claimed by the site extractor.
+        let site = extract_site(html, "https://example.com/p/1");
+        assert_eq!(site["title"], "Widget");
+        assert_eq!(site["price"], "$9.99");
+
+        // An unknown host falls back to the generic extractor.
+        let generic = extract_site(html, "https://other.test/p/1");
+        assert!(generic.get("jsonld").is_some());
+        assert!(generic.get("title").is_none());
+    }
+
+    #[test]
+    fn test_extract_readable() {
+        let html = r#"
+        <html>
+        <head><title>Great Article</title><meta name="author" content="Jane Doe"></head>
+        <body>
+            <div id="sidebar"><p>Related links, ads and navigation clutter here.</p></div>
+            <article>
+                <div class="content">
+                    <p>This is the first substantial paragraph of the article, long enough, with commas, to score well.</p>
+                    <p>Here is a second paragraph that continues the story with even more detail and punctuation, etc.</p>
+                </div>
+            </article>
+            <div class="comment"><p>First! Great post, thanks for the long detailed writeup here.</p></div>
+            <img src="/images/hero.png">
+        </body>
+        </html>
+        "#;
+
+        let article = extract_readable(html, "https://example.com/blog/post");
+        assert_eq!(article.title.as_deref(), Some("Great Article"));
+        assert_eq!(article.byline.as_deref(), Some("Jane Doe"));
+        assert!(article.content.contains("first substantial paragraph"));
+        // Boilerplate is stripped.
+        assert!(!article.content.contains("ads and navigation"));
+        assert!(!article.content.contains("First!"));
+    }
+
+    #[test]
+    fn test_site_extractor_registry() {
+        let site = SiteExtractor {
+            key: "shop".to_string(),
+            host_glob: Some("*.shop.test".to_string()),
+            path_regex: Some(r"^/product/".to_string()),
+            priority: 10,
+            specs: vec![ExtractSpec {
+                source: "css".to_string(),
+                selector: Some("h1".to_string()),
+                accessor: Some("text".to_string()),
+                alias: "title".to_string(),
+                ..Default::default()
+            }],
+        };
+
+        assert!(site.matches("https://www.shop.test/product/42"));
+        assert!(!site.matches("https://www.shop.test/about"));
+        assert!(!site.matches("https://other.test/product/42"));
+
+        let mut registry = ExtractorRegistry::new();
+        registry.register(Box::new(site));
+
+        let html = r#"<html><body><h1>Widget</h1></body></html>"#;
+        let value = registry.extract(html, "https://www.shop.test/product/42");
+        assert_eq!(value["title"], "Widget");
+
+        // A non-matching URL falls back to the generic extractor.
+        let generic = registry.extract(html, "https://www.shop.test/about");
+        assert!(generic.get("jsonld").is_some());
+    }
+
+    #[test]
+    fn test_site_extractors_from_config() {
+        let config = r#"[
+            {
+                "key": "shop",
+                "host_glob": "*.shop.test",
+                "path_regex": "^/product/",
+                "priority": 10,
+                "specs": [
+                    {"source": "css", "selector": "h1", "accessor": "text", "alias": "title"}
+                ]
+            }
+        ]"#;
+
+        let sites = load_site_extractors(config).unwrap();
+        assert_eq!(sites.len(), 1);
+
+        let registry = ExtractorRegistry::with_site_extractors(sites);
+        let html = r#"<html><body><h1>Widget</h1></body></html>"#;
+        let value = registry.extract(html, "https://www.shop.test/product/42");
+        assert_eq!(value["title"], "Widget");
+
+        assert!(load_site_extractors("not json").is_err());
+    }
+
+    #[test]
+    fn test_jsonld_typed_selection() {
+        let html = r#"
+        <script type="application/ld+json">
+        {"@context": "https://schema.org", "@type": "Product", "sku": "A", "name": "First"}
+        </script>
+        <script type="application/ld+json">
+        {"@context": "https://schema.org", "@type": "Product", "sku": "B", "name": "Second"}
+        </script>
+        "#;
+
+        let indexed = ExtractSpec {
+            source: "jsonld".to_string(),
+            alias: "second".to_string(),
+            path: vec!["Product[1]".to_string(), "name".to_string()],
+            return_text: true,
+            ..Default::default()
+        };
+        let filtered = ExtractSpec {
+            source: "jsonld".to_string(),
+            alias: "bySku".to_string(),
+            path: vec!["Product{sku=A}".to_string(), "name".to_string()],
+            return_text: true,
+            ..Default::default()
+        };
+        let wildcard = ExtractSpec {
+            source: "jsonld".to_string(),
+            alias: "names".to_string(),
+            path: vec!["Product[*]".to_string(), "name".to_string()],
+            return_text: true,
+            ..Default::default()
+        };
+
+        let result = extract_all(
+            html,
+            &ExtractionRequest {
+                specs: vec![indexed, filtered, wildcard],
+            },
+        );
+        assert_eq!(result.values["second"], Some("Second".to_string()));
+        assert_eq!(result.values["bySku"], Some("First".to_string()));
+        assert_eq!(
+            result.expanded_values["names"],
+            vec!["First".to_string(), "Second".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_extract_path_jsonpath() {
+        let html = r#"
+        <div data-json='{"items":[{"id":1,"tags":["a","b"]},{"id":2,"tags":["c"]}],"meta":{"nested":{"price":9}}}'></div>
+        "#;
+
+        // Wildcard expansion yields one row per element.
+        let ids = extract_path(html, "div@data-json.items[*].id").unwrap();
+        assert_eq!(ids, serde_json::json!([1, 2]));
+
+        // Nested wildcards flatten across levels.
+        let tags = extract_path(html, "div@data-json.items[*].tags[*]").unwrap();
+        assert_eq!(tags, serde_json::json!(["a", "b", "c"]));
+
+        // Recursive descent finds the price at any depth.
+        let price = extract_path(html, "div@data-json..price").unwrap();
+        assert_eq!(price, serde_json::json!([9]));
+
+        // Slices and negative indices.
+        let first = extract_path(html, "div@data-json.items[0:1].id").unwrap();
+        assert_eq!(first, serde_json::json!([1]));
+        let last = extract_path(html, "div@data-json.items[-1].id").unwrap();
+        assert_eq!(last, serde_json::json!(2));
+    }
+
+    #[test]
+    fn test_extract_path_filter() {
+        let html = r#"
+        <div data-json='{"offers":[{"sku":"A","price":40,"active":true},{"sku":"B","price":15,"active":false},{"sku":"C","price":10,"active":true}]}'></div>
+        "#;
+
+        // Numeric predicate, then project a field out of the survivors.
+        let cheap = extract_path(html, "div@data-json.offers[?(@.price<20)].sku").unwrap();
+        assert_eq!(cheap, serde_json::json!(["B", "C"]));
+
+        // Boolean equality.
+        let active = extract_path(html, "div@data-json.offers[?(@.active==true)].sku").unwrap();
+        assert_eq!(active, serde_json::json!(["A", "C"]));
+
+        // String equality selects a single entity (still plural output).
+        let bySku = extract_path(html, "div@data-json.offers[?(@.sku=='A')].price").unwrap();
+        assert_eq!(bySku, serde_json::json!([40]));
+    }
+
+    #[test]
+    fn test_template_rendering() {
+        let html = r#"
+        <div class="name">Widget</div>
+        <div class="price">19.99</div>
+        <div data-json='["a.jpg","b.jpg"]'></div>
+        "#;
+
+        let specs = vec![
+            ExtractSpec {
+                source: "css".to_string(),
+                selector: Some(".name".to_string()),
+                accessor: Some("text".to_string()),
+                alias: "name".to_string(),
+                ..Default::default()
+            },
+            ExtractSpec {
+                source: "css".to_string(),
+                selector: Some(".price".to_string()),
+                accessor: Some("text".to_string()),
+                alias: "price".to_string(),
+                ..Default::default()
+            },
+            ExtractSpec {
+                source: "css".to_string(),
+                selector: Some("div[data-json]".to_string()),
+                accessor: Some("attr:data-json".to_string()),
+                is_json_cast: true,
+                alias: "images".to_string(),
+                ..Default::default()
+            },
+            ExtractSpec {
+                source: "template".to_string(),
+                template: Some("${{price}} — {{name}}".to_string()),
+                alias: "label".to_string(),
+                ..Default::default()
+            },
+            ExtractSpec {
+                source: "template".to_string(),
+                template: Some("{{#each images}}{{this}}\n{{/each}}".to_string()),
+                alias: "gallery".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let result = extract_all(html, &ExtractionRequest { specs });
+        assert_eq!(result.values["label"], Some("$19.99 — Widget".to_string()));
+        assert_eq!(result.values["gallery"], Some("a.jpg\nb.jpg\n".to_string()));
+    }
+
     #[test]
     fn test_css_extraction() {
         let html = r#"
@@ -1188,6 +3245,10 @@ fn test_full_extraction() {
                 expand_array: false,
                 array_field: None,
                 json_path: None,
+                children: vec![],
+                scope_selector: None,
+                scope_path: vec![],
+                template: None,
             },
             ExtractSpec {
                 source: "jsonld".to_string(),
@@ -1201,6 +3262,10 @@ fn test_full_extraction() {
                 expand_array: false,
                 array_field: None,
                 json_path: None,
+                children: vec![],
+                scope_selector: None,
+                scope_path: vec![],
+                template: None,
             },
             ExtractSpec {
                 source: "css".to_string(),
@@ -1214,6 +3279,10 @@ fn test_full_extraction() {
                 expand_array: false,
                 array_field: None,
                 json_path: None,
+                children: vec![],
+                scope_selector: None,
+                scope_path: vec![],
+                template: None,
             },
         ],
     };
@@ -1362,6 +3431,35 @@ fn test_js_extraction_json_parse() {
     assert_eq!(meta["page_id"], "abc");
 }
 
+#[test]
+fn test_js_extraction_member_targets() {
+    let html = r#"
+    <html>
+    <head>
+        <script>
+        window.__NEXT_DATA__ = {"props":{"id":"42"}};
+        self.__remixContext = {"url":"/p/1"};
+        app.data.config = {"locale":"en"};
+        (function(){ window.__INITIAL_STATE__ = JSON.parse('{"cart":[]}'); })();
+        </script>
+    </head>
+    </html>
+    "#;
+
+    let document = Html::parse_document(html);
+    let js_vars = extract_js_variables(&document);
+
+    // Global receiver is stripped.
+    assert_eq!(js_vars["__NEXT_DATA__"]["props"]["id"], "42");
+    assert_eq!(js_vars["__remixContext"]["url"], "/p/1");
+
+    // Deeper chains are kept verbatim.
+    assert_eq!(js_vars["app.data.config"]["locale"], "en");
+
+    // Assignment inside an IIFE is still discovered.
+    assert!(js_vars["__INITIAL_STATE__"]["cart"].is_array());
+}
+
 #[test]
 fn test_js_extraction_escaped_strings() {
     let html = r#"
@@ -1427,6 +3525,43 @@ fn test_extract_from_js() {
     assert!(result.unwrap().contains("company_name"));
 }
 
+#[test]
+fn test_query_json() {
+    let mut data = HashMap::new();
+    data.insert(
+        "meta".to_string(),
+        serde_json::json!({"org_info": {"company_name": "Test Corp"}}),
+    );
+    data.insert(
+        "results".to_string(),
+        serde_json::json!([{"href": "/a"}, {"href": "/b"}]),
+    );
+
+    // Dotted path to a single scalar.
+    assert_eq!(
+        query_json(&data, "meta.org_info.company_name"),
+        vec!["Test Corp".to_string()]
+    );
+
+    // Wildcard projection.
+    assert_eq!(
+        query_json(&data, "results[*].href"),
+        vec!["/a".to_string(), "/b".to_string()]
+    );
+
+    // Index access.
+    assert_eq!(query_json(&data, "results[1].href"), vec!["/b".to_string()]);
+
+    // Recursive descent.
+    assert_eq!(
+        query_json(&data, "meta..company_name"),
+        vec!["Test Corp".to_string()]
+    );
+
+    // Non-scalar matches are skipped.
+    assert!(query_json(&data, "meta.org_info").is_empty());
+}
+
 #[test]
 fn test_extract_links() {
     let html = r##"<!DOCTYPE html>
@@ -1451,3 +3586,68 @@ fn test_extract_links() {
     assert!(!links.iter().any(|l| l.contains("mailto:")));
     assert!(!links.iter().any(|l| l.contains("#anchor")));
 }
+
+#[test]
+fn test_autolink() {
+    let text = "See https://example.com/page. Visit www.rust-lang.org now or mail a@b.com? no";
+    let linked = autolink(text, &[("rel", "nofollow")]);
+
+    assert!(linked.contains(r#"<a href="https://example.com/page" rel="nofollow">https://example.com/page</a>."#));
+    assert!(linked.contains(r#"<a href="http://www.rust-lang.org" rel="nofollow">www.rust-lang.org</a>"#));
+    // Trailing punctuation stays outside the anchor.
+    assert!(linked.contains("</a>."));
+
+    // Existing anchors are left untouched.
+    let marked = r#"<a href="/x">https://example.com</a> and https://other.com"#;
+    let out = autolink(marked, &[]);
+    assert!(out.contains(r#"<a href="/x">https://example.com</a>"#));
+    assert!(out.contains(r#"<a href="https://other.com">https://other.com</a>"#));
+}
+
+#[test]
+fn test_extract_links_filter() {
+    let html = r##"<!DOCTYPE html>
+    <html>
+    <body>
+        <a href="/internal">Internal</a>
+        <a href="https://shop.example.com/p">Subdomain</a>
+        <a href="https://ad.doubleclick.net/x">Tracker</a>
+        <a href="https://partner.test/x">Partner</a>
+    </body>
+    </html>"##;
+
+    let same_origin = extract_links_filtered(
+        html,
+        "a[href]",
+        "https://base.com/dir/",
+        &LinkFilter {
+            same_origin_only: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(same_origin, vec!["https://base.com/internal".to_string()]);
+
+    let denied = extract_links_filtered(
+        html,
+        "a[href]",
+        "https://base.com/dir/",
+        &LinkFilter {
+            deny_domains: vec!["doubleclick.net".to_string()],
+            ..Default::default()
+        },
+    );
+    assert!(!denied.iter().any(|l| l.contains("doubleclick")));
+    assert!(denied.iter().any(|l| l.contains("partner.test")));
+
+    let allowed = extract_links_filtered(
+        html,
+        "a[href]",
+        "https://base.com/dir/",
+        &LinkFilter {
+            allow_domains: vec!["example.com".to_string()],
+            ..Default::default()
+        },
+    );
+    // Suffix match keeps the subdomain, drops everything else.
+    assert_eq!(allowed, vec!["https://shop.example.com/p".to_string()]);
+}
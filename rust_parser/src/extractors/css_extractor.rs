@@ -2,7 +2,8 @@
 //!
 //! Uses the scraper crate to select elements by CSS selectors.
 
-use scraper::{Html, Selector};
+use scraper::{Html, Node, Selector};
+use std::collections::{HashMap, HashSet};
 
 /// Extract elements matching a CSS selector
 /// Returns outer HTML of matching elements
@@ -74,6 +75,136 @@ pub fn extract_css_first_attr(html: &str, selector_str: &str, attr_name: &str) -
         .and_then(|el| el.value().attr(attr_name).map(String::from))
 }
 
+/// Configuration for [`extract_css_sanitized`].
+///
+/// The default profile drops `<script>`/`<style>` subtrees and every
+/// `on*` event-handler attribute, and rewrites resource-loading attributes
+/// (`src`/`srcset`/`poster`) to their `data-` equivalents so extracted
+/// fragments don't auto-fetch remote assets when rendered.
+#[derive(Debug, Clone)]
+pub struct SanitizeOptions {
+    /// When `Some`, only these tag names survive; otherwise all non-dropped tags do.
+    pub allowed_tags: Option<HashSet<String>>,
+    /// When `Some`, only these attribute names survive.
+    pub allowed_attrs: Option<HashSet<String>>,
+    /// Tag names whose entire subtree is removed.
+    pub drop_tags: HashSet<String>,
+    /// Attribute renames applied after the allow checks (e.g. `src` -> `data-src`).
+    pub attr_renames: HashMap<String, String>,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        let drop_tags = ["script", "style"].iter().map(|s| s.to_string()).collect();
+        let attr_renames = [
+            ("src", "data-src"),
+            ("srcset", "data-srcset"),
+            ("poster", "data-poster"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        Self {
+            allowed_tags: None,
+            allowed_attrs: None,
+            drop_tags,
+            attr_renames,
+        }
+    }
+}
+
+/// HTML void elements, which are serialized without a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Extract elements matching `selector` and return their sanitized outer HTML.
+///
+/// Each match is walked over the existing [`Html`] parse tree and re-serialized
+/// according to `opts`, so callers can archive page fragments without embedded
+/// tracking pixels or active content. See [`SanitizeOptions`].
+pub fn extract_css_sanitized(html: &str, selector_str: &str, opts: &SanitizeOptions) -> Vec<String> {
+    let document = Html::parse_document(html);
+
+    let selector = match Selector::parse(selector_str) {
+        Ok(s) => s,
+        Err(_) => return vec![],
+    };
+
+    document
+        .select(&selector)
+        .map(|el| {
+            let mut out = String::new();
+            serialize_sanitized(*el, &mut out, opts);
+            out
+        })
+        .collect()
+}
+
+/// Recursively serialize a node, applying the sanitization rules.
+fn serialize_sanitized(node: ego_tree::NodeRef<Node>, out: &mut String, opts: &SanitizeOptions) {
+    match node.value() {
+        Node::Text(text) => out.push_str(&escape_text(text)),
+        Node::Element(element) => {
+            let name = element.name();
+
+            if opts.drop_tags.contains(name) {
+                return;
+            }
+            if let Some(allowed) = &opts.allowed_tags {
+                if !allowed.contains(name) {
+                    return;
+                }
+            }
+
+            out.push('<');
+            out.push_str(name);
+            for (attr, value) in element.attrs() {
+                // Drop event handlers outright.
+                if attr.starts_with("on") {
+                    continue;
+                }
+                if let Some(allowed) = &opts.allowed_attrs {
+                    if !allowed.contains(attr) {
+                        continue;
+                    }
+                }
+                let attr_name = opts.attr_renames.get(attr).map(|s| s.as_str()).unwrap_or(attr);
+                out.push(' ');
+                out.push_str(attr_name);
+                out.push_str("=\"");
+                out.push_str(&escape_attr(value));
+                out.push('"');
+            }
+
+            if VOID_ELEMENTS.contains(&name) {
+                out.push_str(" />");
+                return;
+            }
+            out.push('>');
+
+            for child in node.children() {
+                serialize_sanitized(child, out, opts);
+            }
+
+            out.push_str("</");
+            out.push_str(name);
+            out.push('>');
+        }
+        _ => {}
+    }
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_attr(s: &str) -> String {
+    escape_text(s).replace('"', "&quot;")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +244,26 @@ mod tests {
         let unit_price = extract_css_first_text(html, "div.product .unit-price");
         assert_eq!(unit_price.unwrap(), "€1.50/kg");
     }
+
+    #[test]
+    fn test_sanitize_default_profile() {
+        let html = r#"
+        <div class="card">
+            <img src="https://tracker.example/pixel.gif" onload="steal()">
+            <script>alert(1)</script>
+            <p>Hello &amp; welcome</p>
+        </div>
+        "#;
+
+        let cleaned = extract_css_sanitized(html, "div.card", &SanitizeOptions::default());
+        assert_eq!(cleaned.len(), 1);
+        let out = &cleaned[0];
+
+        // script subtree removed, event handler dropped, src rewritten.
+        assert!(!out.contains("<script"));
+        assert!(!out.contains("onload"));
+        assert!(out.contains("data-src=\"https://tracker.example/pixel.gif\""));
+        assert!(!out.contains(" src="));
+        assert!(out.contains("Hello &amp; welcome"));
+    }
 }
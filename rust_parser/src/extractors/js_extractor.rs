@@ -7,12 +7,16 @@
 
 use scraper::{Html, Selector};
 use serde_json::{Map, Value};
-use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+use tree_sitter::{Node, Parser, Query, QueryCursor, StreamingIterator};
 
 /// Extract JavaScript variables from HTML script tags
 pub fn extract_js_variables(html: &str) -> Value {
     let document = Html::parse_document(html);
+    extract_js_variables_doc(&document)
+}
 
+/// Extract JavaScript variables from an already-parsed document.
+pub fn extract_js_variables_doc(document: &Html) -> Value {
     // Select script tags (not type="application/ld+json" or similar)
     let selector = match Selector::parse("script:not([type])") {
         Ok(s) => s,
@@ -72,19 +76,24 @@ fn extract_variables_from_js(js_code: &str) -> Vec<(String, Value)> {
         None => return extract_variables_regex(js_code),
     };
 
-    // Query for variable declarations and assignments
+    // Query for variable declarations, member assignments of any depth, and
+    // analytics-style calls. Values are captured as whole expressions so
+    // `JSON.parse("...")` can be recognized alongside object/array literals.
     let query_str = r#"
         ; var/let/const declarations
         (variable_declarator
             name: (identifier) @var_name
-            value: [(object) (array)] @var_value)
+            value: [(object) (array) (call_expression)] @var_value)
 
-        ; window.X = {...} assignments
+        ; member assignments: window.X, window.A.b.c, App.store, ...
         (assignment_expression
-            left: (member_expression
-                object: (identifier) @obj_name
-                property: (property_identifier) @prop_name)
-            right: [(object) (array)] @assign_value)
+            left: (member_expression) @assign_target
+            right: [(object) (array) (call_expression)] @assign_value)
+
+        ; analytics calls: dataLayer.push(...), gtag(...), ga(...)
+        (call_expression
+            function: (_) @call_callee
+            arguments: (arguments) @call_args)
     "#;
 
     let query = match Query::new(&language.into(), query_str) {
@@ -94,48 +103,114 @@ fn extract_variables_from_js(js_code: &str) -> Vec<(String, Value)> {
 
     let mut cursor = QueryCursor::new();
 
+    // Repeated analytics pushes accumulate under a single synthesized key.
+    let mut data_layer: Vec<Value> = Vec::new();
+
     let mut matches_iter = cursor.matches(&query, tree.root_node(), js_code.as_bytes());
     while let Some(m) = matches_iter.next() {
         let mut var_name: Option<String> = None;
-        let mut var_value: Option<&str> = None;
-        let mut obj_name: Option<String> = None;
-        let mut prop_name: Option<String> = None;
-        let mut assign_value: Option<&str> = None;
+        let mut var_value: Option<Node> = None;
+        let mut assign_target: Option<Node> = None;
+        let mut assign_value: Option<Node> = None;
+        let mut call_callee: Option<Node> = None;
+        let mut call_args: Option<Node> = None;
 
         for capture in m.captures {
             let capture_name: &str = query.capture_names()[capture.index as usize];
-            let node_text = &js_code[capture.node.byte_range()];
 
             match capture_name {
-                "var_name" => var_name = Some(node_text.to_string()),
-                "var_value" => var_value = Some(node_text),
-                "obj_name" => obj_name = Some(node_text.to_string()),
-                "prop_name" => prop_name = Some(node_text.to_string()),
-                "assign_value" => assign_value = Some(node_text),
+                "var_name" => var_name = Some(js_code[capture.node.byte_range()].to_string()),
+                "var_value" => var_value = Some(capture.node),
+                "assign_target" => assign_target = Some(capture.node),
+                "assign_value" => assign_value = Some(capture.node),
+                "call_callee" => call_callee = Some(capture.node),
+                "call_args" => call_args = Some(capture.node),
                 _ => {}
             }
         }
 
-        // Handle variable declarations
-        if let (Some(name), Some(value_str)) = (var_name, var_value) {
-            if let Ok(value) = parse_js_value(value_str) {
+        // Handle variable declarations, building the value from the AST.
+        if let (Some(name), Some(node)) = (var_name, var_value) {
+            if let Some(value) = node_to_value(node, js_code) {
                 results.push((name, value));
             }
         }
 
-        // Handle window.X assignments
-        if let (Some(obj), Some(prop), Some(value_str)) = (obj_name, prop_name, assign_value) {
-            if obj == "window" {
-                if let Ok(value) = parse_js_value(value_str) {
-                    results.push((prop, value));
+        // Handle member assignments at any depth, nesting everything below the
+        // leading `window` (if present) under the first real segment.
+        if let (Some(target), Some(node)) = (assign_target, assign_value) {
+            if let Some(mut path) = member_path(target, js_code) {
+                if path.first().map(|s| s.as_str()) == Some("window") {
+                    path.remove(0);
+                }
+                if !path.is_empty() {
+                    if let Some(value) = node_to_value(node, js_code) {
+                        let (key, nested) = nest_value(&path, value);
+                        results.push((key, nested));
+                    }
+                }
+            }
+        }
+
+        // Collect object/array arguments of analytics calls.
+        if let (Some(callee), Some(args)) = (call_callee, call_args) {
+            if let Some(path) = member_path(callee, js_code) {
+                let callee_name = path.join(".");
+                if matches!(callee_name.as_str(), "dataLayer.push" | "gtag" | "ga") {
+                    let mut arg_cursor = args.walk();
+                    for arg in args.named_children(&mut arg_cursor) {
+                        if matches!(arg.kind(), "object" | "array") {
+                            if let Some(value) = node_to_value(arg, js_code) {
+                                data_layer.push(value);
+                            }
+                        }
+                    }
                 }
             }
         }
     }
 
+    if !data_layer.is_empty() {
+        results.push(("dataLayer".to_string(), Value::Array(data_layer)));
+    }
+
     results
 }
 
+/// Flatten a `member_expression` (or bare `identifier`) into its dotted
+/// components, e.g. `window.App.store` -> `["window", "App", "store"]`.
+///
+/// Returns `None` if any segment is computed (`a[b]`) or otherwise not a plain
+/// identifier, so callers don't synthesize keys from dynamic lookups.
+fn member_path(node: Node, src: &str) -> Option<Vec<String>> {
+    match node.kind() {
+        "identifier" => Some(vec![src[node.byte_range()].to_string()]),
+        "member_expression" => {
+            let object = node.child_by_field_name("object")?;
+            let property = node.child_by_field_name("property")?;
+            if property.kind() != "property_identifier" {
+                return None;
+            }
+            let mut path = member_path(object, src)?;
+            path.push(src[property.byte_range()].to_string());
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Turn a dotted path and a leaf value into a `(key, nested_value)` pair, e.g.
+/// `(["A", "b", "c"], V)` -> `("A", {"b": {"c": V}})`. The path must be non-empty.
+fn nest_value(path: &[String], value: Value) -> (String, Value) {
+    let mut current = value;
+    for seg in path[1..].iter().rev() {
+        let mut map = Map::new();
+        map.insert(seg.clone(), current);
+        current = Value::Object(map);
+    }
+    (path[0].clone(), current)
+}
+
 /// Fallback regex-based extraction for when tree-sitter fails
 fn extract_variables_regex(js_code: &str) -> Vec<(String, Value)> {
     let mut results = Vec::new();
@@ -174,6 +249,132 @@ fn extract_variables_regex(js_code: &str) -> Vec<(String, Value)> {
     results
 }
 
+/// Build a `serde_json::Value` directly from a tree-sitter value node.
+///
+/// Walking the AST avoids the lossy string-repair path in [`parse_js_value`],
+/// so values containing apostrophes, braces inside strings, or escape sequences
+/// survive intact. Values that can't be represented in JSON (functions,
+/// identifiers, template expressions, ...) are skipped rather than forced.
+pub(crate) fn node_to_value(node: Node, src: &str) -> Option<Value> {
+    match node.kind() {
+        "object" => {
+            let mut map = Map::new();
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if child.kind() != "pair" {
+                    continue;
+                }
+                let key = child
+                    .child_by_field_name("key")
+                    .and_then(|k| node_key_to_string(k, src));
+                let value = child
+                    .child_by_field_name("value")
+                    .and_then(|v| node_to_value(v, src));
+                if let (Some(key), Some(value)) = (key, value) {
+                    map.insert(key, value);
+                }
+            }
+            Some(Value::Object(map))
+        }
+        "array" => {
+            let mut arr = Vec::new();
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if let Some(value) = node_to_value(child, src) {
+                    arr.push(value);
+                }
+            }
+            Some(Value::Array(arr))
+        }
+        "string" => Some(Value::String(parse_js_string(&src[node.byte_range()]))),
+        "number" => parse_js_number(&src[node.byte_range()]),
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "null" => Some(Value::Null),
+        // Recognize `JSON.parse("...")` and decode its string literal argument.
+        "call_expression" => {
+            let callee = node.child_by_field_name("function")?;
+            if member_path(callee, src)?.join(".") != "JSON.parse" {
+                return None;
+            }
+            let args = node.child_by_field_name("arguments")?;
+            let mut cursor = args.walk();
+            for arg in args.named_children(&mut cursor) {
+                if arg.kind() == "string" {
+                    let decoded = parse_js_string(&src[arg.byte_range()]);
+                    return serde_json::from_str(&decoded).ok();
+                }
+            }
+            None
+        }
+        // function, identifier, template_string, ... are skipped.
+        _ => None,
+    }
+}
+
+/// Resolve an object key node (`property_identifier`, `string`, `number`).
+fn node_key_to_string(node: Node, src: &str) -> Option<String> {
+    match node.kind() {
+        "property_identifier" | "identifier" => Some(src[node.byte_range()].to_string()),
+        "string" => Some(parse_js_string(&src[node.byte_range()])),
+        "number" => Some(src[node.byte_range()].to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a numeric literal into a JSON number.
+fn parse_js_number(text: &str) -> Option<Value> {
+    if let Ok(i) = text.parse::<i64>() {
+        Some(Value::Number(i.into()))
+    } else if let Ok(f) = text.parse::<f64>() {
+        serde_json::Number::from_f64(f).map(Value::Number)
+    } else {
+        None
+    }
+}
+
+/// Strip the surrounding quotes from a string literal and unescape its body.
+fn parse_js_string(raw: &str) -> String {
+    let trimmed = if raw.len() >= 2
+        && (raw.starts_with('"') || raw.starts_with('\'') || raw.starts_with('`'))
+    {
+        &raw[1..raw.len() - 1]
+    } else {
+        raw
+    };
+    unescape_js(trimmed)
+}
+
+/// Decode the common JavaScript string escape sequences.
+fn unescape_js(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('b') => out.push('\u{0008}'),
+            Some('f') => out.push('\u{000C}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if let Some(ch) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    out.push(ch);
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+
+    out
+}
+
 /// Parse a JavaScript value string to JSON
 /// Handles trailing commas, single quotes, and unquoted keys
 fn parse_js_value(js_str: &str) -> Result<Value, serde_json::Error> {
@@ -247,4 +448,60 @@ mod tests {
         let v4 = parse_js_value(r#"{name: "test"}"#).unwrap();
         assert_eq!(v4["name"].as_str().unwrap(), "test");
     }
+
+    #[test]
+    fn test_ast_value_construction() {
+        // Values with apostrophes and a function member that cannot be
+        // represented in JSON; the apostrophe must survive and the function
+        // must be dropped rather than corrupting the surrounding object.
+        let html = r#"
+        <script>
+            var data = {
+                name: "it's here",
+                count: 3,
+                active: true,
+                tags: ["a", "b"],
+                handler: function() { return 1; }
+            };
+        </script>
+        "#;
+
+        let result = extract_js_variables(html);
+        let data = &result["data"];
+
+        // Regex repair would have mangled the apostrophe; the AST path keeps it.
+        assert_eq!(data["name"].as_str().unwrap(), "it's here");
+        assert_eq!(data["count"].as_i64().unwrap(), 3);
+        assert_eq!(data["active"].as_bool().unwrap(), true);
+        assert_eq!(data["tags"][0].as_str().unwrap(), "a");
+        assert_eq!(data["tags"][1].as_str().unwrap(), "b");
+        // The function value is skipped, leaving the rest of the object intact.
+        assert!(data.get("handler").is_none());
+    }
+
+    #[test]
+    fn test_analytics_and_deep_assignments() {
+        let html = r#"
+        <script>
+            dataLayer.push({ event: "view_item", value: 42 });
+            gtag('event', 'purchase', { transaction_id: "T1" });
+            window.App.store = { cart: 3 };
+            window.__STATE__ = JSON.parse("{\"user\": {\"id\": 7}}");
+        </script>
+        "#;
+
+        let result = extract_js_variables(html);
+
+        // Repeated pushes accumulate into an array under `dataLayer`.
+        let dl = result["dataLayer"].as_array().unwrap();
+        assert_eq!(dl.len(), 2);
+        assert_eq!(dl[0]["event"].as_str().unwrap(), "view_item");
+        assert_eq!(dl[1]["transaction_id"].as_str().unwrap(), "T1");
+
+        // Deep member chains nest below the first non-window segment.
+        assert_eq!(result["App"]["store"]["cart"].as_i64().unwrap(), 3);
+
+        // JSON.parse string literals are decoded into a Value.
+        assert_eq!(result["__STATE__"]["user"]["id"].as_i64().unwrap(), 7);
+    }
 }
@@ -6,7 +6,18 @@
 use scraper::{Html, Selector};
 use serde_json::{Map, Value};
 
-/// Extract JSON-LD data from HTML, keyed by @type
+/// Maximum depth to which `@id` references are recursively inlined.
+const MAX_INLINE_DEPTH: usize = 6;
+
+/// Extract JSON-LD data from HTML, keyed by @type.
+///
+/// Objects are flattened before bucketing: a first pass collects every node
+/// carrying an `@id` (including those nested inside `@graph`) into a map, then
+/// bare `{"@id": "..."}` references are replaced with a clone of the node they
+/// point at so that, e.g., a `Product`'s `brand` pointing at an `Organization`
+/// node gets inlined. When a block's `@context` is `schema.org`, the common
+/// `schema:` / `http://schema.org/` prefixes are stripped from type and
+/// property keys so the output is consistently keyed.
 pub fn extract_jsonld(html: &str) -> Value {
     let document = Html::parse_document(html);
 
@@ -16,8 +27,7 @@ pub fn extract_jsonld(html: &str) -> Value {
         Err(_) => return Value::Object(Map::new()),
     };
 
-    let mut result: Map<String, Value> = Map::new();
-
+    let mut docs: Vec<Value> = Vec::new();
     for element in document.select(&selector) {
         let content = element.inner_html();
         let trimmed = content.trim();
@@ -26,21 +36,63 @@ pub fn extract_jsonld(html: &str) -> Value {
             continue;
         }
 
-        // Parse JSON
         if let Ok(json) = serde_json::from_str::<Value>(trimmed) {
-            process_jsonld_value(&json, &mut result);
+            docs.push(json);
         }
     }
 
+    // Phase 1: build the node map keyed by @id across every block.
+    let mut nodes: Map<String, Value> = Map::new();
+    for doc in &docs {
+        collect_nodes(doc, &mut nodes);
+    }
+
+    // Phase 2: inline references, normalize keys and bucket by @type.
+    let mut result: Map<String, Value> = Map::new();
+    for doc in &docs {
+        let schema = is_schema_org_context(doc);
+        process_jsonld_value(doc, &nodes, schema, &mut result);
+    }
+
     Value::Object(result)
 }
 
-fn process_jsonld_value(value: &Value, result: &mut Map<String, Value>) {
+/// Recursively collect every object carrying an `@id` into `nodes`.
+///
+/// Bare `{"@id": "..."}` stubs are skipped so they don't clobber the real
+/// node defined elsewhere in the document.
+fn collect_nodes(value: &Value, nodes: &mut Map<String, Value>) {
+    match value {
+        Value::Object(obj) => {
+            if obj.len() > 1 {
+                if let Some(Value::String(id)) = obj.get("@id") {
+                    nodes.entry(id.clone()).or_insert_with(|| value.clone());
+                }
+            }
+            for v in obj.values() {
+                collect_nodes(v, nodes);
+            }
+        }
+        Value::Array(arr) => {
+            for v in arr {
+                collect_nodes(v, nodes);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn process_jsonld_value(
+    value: &Value,
+    nodes: &Map<String, Value>,
+    schema: bool,
+    result: &mut Map<String, Value>,
+) {
     match value {
         Value::Array(arr) => {
             // Array of JSON-LD objects
             for item in arr {
-                process_jsonld_value(item, result);
+                process_jsonld_value(item, nodes, schema, result);
             }
         }
         Value::Object(obj) => {
@@ -48,19 +100,29 @@ fn process_jsonld_value(value: &Value, result: &mut Map<String, Value>) {
             if let Some(graph) = obj.get("@graph") {
                 if let Value::Array(graph_items) = graph {
                     for item in graph_items {
-                        process_jsonld_object(item, result);
+                        process_jsonld_object(item, nodes, schema, result);
                     }
                 }
             } else {
-                process_jsonld_object(value, result);
+                process_jsonld_object(value, nodes, schema, result);
             }
         }
         _ => {}
     }
 }
 
-fn process_jsonld_object(value: &Value, result: &mut Map<String, Value>) {
-    if let Value::Object(obj) = value {
+fn process_jsonld_object(
+    value: &Value,
+    nodes: &Map<String, Value>,
+    schema: bool,
+    result: &mut Map<String, Value>,
+) {
+    // Inline @id references, then normalize schema.org key prefixes.
+    let mut visited: Vec<String> = Vec::new();
+    let inlined = inline_refs(value, nodes, &mut visited, 0);
+    let normalized = normalize_value(&inlined, schema);
+
+    if let Value::Object(obj) = &normalized {
         // Get @type
         let type_key = if let Some(type_val) = obj.get("@type") {
             match type_val {
@@ -80,20 +142,122 @@ fn process_jsonld_object(value: &Value, result: &mut Map<String, Value>) {
             if let Some(existing) = result.get_mut(&type_name) {
                 match existing {
                     Value::Array(arr) => {
-                        arr.push(value.clone());
+                        arr.push(normalized.clone());
                     }
                     _ => {
                         let old = existing.clone();
-                        *existing = Value::Array(vec![old, value.clone()]);
+                        *existing = Value::Array(vec![old, normalized.clone()]);
                     }
                 }
             } else {
-                result.insert(type_name, value.clone());
+                result.insert(type_name, normalized.clone());
+            }
+        }
+    }
+}
+
+/// Replace `{"@id": "..."}`-only stubs with a clone of the referenced node.
+///
+/// `visited` tracks the `@id`s on the current inlining path so reference
+/// cycles terminate; `depth` bounds the recursion at [`MAX_INLINE_DEPTH`].
+fn inline_refs(
+    value: &Value,
+    nodes: &Map<String, Value>,
+    visited: &mut Vec<String>,
+    depth: usize,
+) -> Value {
+    match value {
+        Value::Object(obj) => {
+            // A bare {"@id": "..."} reference — inline the target node.
+            if obj.len() == 1 {
+                if let Some(Value::String(id)) = obj.get("@id") {
+                    if depth < MAX_INLINE_DEPTH && !visited.contains(id) {
+                        if let Some(node) = nodes.get(id) {
+                            visited.push(id.clone());
+                            let inlined = inline_refs(node, nodes, visited, depth + 1);
+                            visited.pop();
+                            return inlined;
+                        }
+                    }
+                    return value.clone();
+                }
+            }
+
+            let mut out = Map::new();
+            for (k, v) in obj {
+                out.insert(k.clone(), inline_refs(v, nodes, visited, depth));
+            }
+            Value::Object(out)
+        }
+        Value::Array(arr) => {
+            Value::Array(arr.iter().map(|v| inline_refs(v, nodes, visited, depth)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Strip the common schema.org prefixes from a type or property key.
+fn normalize_key(key: &str) -> String {
+    for prefix in ["http://schema.org/", "https://schema.org/", "schema:"] {
+        if let Some(rest) = key.strip_prefix(prefix) {
+            return rest.to_string();
+        }
+    }
+    key.to_string()
+}
+
+/// Normalize every key (and `@type` value) in a schema.org document tree.
+///
+/// A no-op when `schema` is false so non-schema.org vocabularies are left
+/// untouched.
+fn normalize_value(value: &Value, schema: bool) -> Value {
+    if !schema {
+        return value.clone();
+    }
+    match value {
+        Value::Object(obj) => {
+            let mut out = Map::new();
+            for (k, v) in obj {
+                let key = normalize_key(k);
+                let val = if key == "@type" {
+                    normalize_type(v)
+                } else {
+                    normalize_value(v, schema)
+                };
+                out.insert(key, val);
             }
+            Value::Object(out)
         }
+        Value::Array(arr) => Value::Array(arr.iter().map(|v| normalize_value(v, schema)).collect()),
+        _ => value.clone(),
     }
 }
 
+/// Strip schema.org prefixes from `@type` string (or array of strings) values.
+fn normalize_type(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(normalize_key(s)),
+        Value::Array(arr) => Value::Array(arr.iter().map(normalize_type).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Whether a block's `@context` declares the schema.org vocabulary.
+fn is_schema_org_context(doc: &Value) -> bool {
+    fn matches(value: &Value) -> bool {
+        match value {
+            Value::String(s) => {
+                let trimmed = s.trim_end_matches('/');
+                trimmed == "https://schema.org" || trimmed == "http://schema.org"
+            }
+            Value::Array(arr) => arr.iter().any(matches),
+            Value::Object(obj) => obj.values().any(matches),
+            _ => false,
+        }
+    }
+    doc.get("@context").map(matches).unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +305,42 @@ mod tests {
         assert!(result.get("Product").is_some());
         assert!(result.get("Organization").is_some());
     }
+
+    #[test]
+    fn test_inline_graph_id_reference() {
+        let html = r#"
+        <script type="application/ld+json">
+        {
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@type": "Product", "name": "Widget", "brand": {"@id": "#brand"}},
+                {"@id": "#brand", "@type": "Organization", "name": "Acme"}
+            ]
+        }
+        </script>
+        "#;
+
+        let result = extract_jsonld(html);
+        // The bare {"@id": "#brand"} stub is replaced by the Organization node.
+        assert_eq!(
+            result["Product"]["brand"]["name"].as_str().unwrap(),
+            "Acme"
+        );
+    }
+
+    #[test]
+    fn test_strip_schema_org_prefixes() {
+        let html = r#"
+        <script type="application/ld+json">
+        {
+            "@context": "https://schema.org",
+            "@type": "schema:Product",
+            "schema:name": "Prefixed"
+        }
+        </script>
+        "#;
+
+        let result = extract_jsonld(html);
+        assert_eq!(result["Product"]["name"].as_str().unwrap(), "Prefixed");
+    }
 }
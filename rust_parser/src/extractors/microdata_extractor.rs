@@ -9,7 +9,11 @@ use serde_json::{Map, Value};
 /// Extract microdata from HTML, keyed by itemtype
 pub fn extract_microdata(html: &str) -> Value {
     let document = Html::parse_document(html);
+    extract_microdata_doc(&document)
+}
 
+/// Extract microdata from an already-parsed document, keyed by itemtype.
+pub fn extract_microdata_doc(document: &Html) -> Value {
     // Find all elements with itemscope (top-level microdata items)
     let selector = match Selector::parse("[itemscope]") {
         Ok(s) => s,
@@ -30,7 +34,7 @@ pub fn extract_microdata(html: &str) -> Value {
             continue;
         }
 
-        let item = extract_item(&element);
+        let item = extract_item(&element, document);
         if let Some(type_name) = item.get("@type").and_then(|v| v.as_str()) {
             let type_key = type_name.to_string();
 
@@ -54,7 +58,7 @@ pub fn extract_microdata(html: &str) -> Value {
     Value::Object(result)
 }
 
-fn extract_item(element: &scraper::ElementRef) -> Value {
+fn extract_item(element: &scraper::ElementRef, document: &Html) -> Value {
     let mut item: Map<String, Value> = Map::new();
 
     // Get itemtype
@@ -78,82 +82,124 @@ fn extract_item(element: &scraper::ElementRef) -> Value {
         Err(_) => return Value::Object(item),
     };
 
+    // Properties that are DOM descendants of the itemscope element.
     for prop_element in element.select(&prop_selector) {
-        // Skip if this property belongs to a nested itemscope
-        let mut found_itemscope = false;
-        let mut current = prop_element.parent();
-
-        while let Some(parent_node) = current {
-            if let Some(parent_elem) = parent_node.value().as_element() {
-                // If we hit the original element, we're good
-                if parent_elem.id() == element.value().id() {
-                    break;
-                }
-                // If we hit another itemscope first, this prop belongs to it
-                if parent_elem.attr("itemscope").is_some() {
-                    found_itemscope = true;
-                    break;
+        if belongs_to_nested_scope(&prop_element, element) {
+            continue;
+        }
+        fold_prop(&mut item, &prop_element, document);
+    }
+
+    // Properties pulled in from elsewhere in the document via `itemref`.
+    if let Some(itemref) = element.value().attr("itemref") {
+        for id in itemref.split_whitespace() {
+            let ref_element = match find_by_id(document, id) {
+                Some(el) => el,
+                None => continue,
+            };
+
+            // A referenced element that is itself a property is folded directly
+            // (recursing into it if it opens a nested itemscope); otherwise its
+            // own itemprop descendants are folded, using it as the boundary.
+            if ref_element.value().attr("itemprop").is_some() {
+                fold_prop(&mut item, &ref_element, document);
+            } else {
+                for prop_element in ref_element.select(&prop_selector) {
+                    if belongs_to_nested_scope(&prop_element, &ref_element) {
+                        continue;
+                    }
+                    fold_prop(&mut item, &prop_element, document);
                 }
             }
-            current = parent_node.parent();
         }
+    }
 
-        if found_itemscope {
-            continue;
+    Value::Object(item)
+}
+
+/// Whether `prop_element` belongs to an itemscope nested below `boundary`
+/// rather than to `boundary` itself.
+fn belongs_to_nested_scope(
+    prop_element: &scraper::ElementRef,
+    boundary: &scraper::ElementRef,
+) -> bool {
+    let mut current = prop_element.parent();
+    while let Some(parent_node) = current {
+        // Reached the boundary node itself: the prop is a direct descendant.
+        // Compare ego-tree node ids, not the HTML `id` attribute (which is
+        // `None` for most itemscope elements and would short-circuit here).
+        if parent_node.id() == boundary.id() {
+            return false;
+        }
+        // Hit an interior itemscope first, so the prop belongs to it.
+        if let Some(parent_elem) = parent_node.value().as_element() {
+            if parent_elem.attr("itemscope").is_some() {
+                return true;
+            }
         }
+        current = parent_node.parent();
+    }
+    false
+}
 
-        // Get property name
-        let prop_name = match prop_element.value().attr("itemprop") {
-            Some(name) => name.to_string(),
-            None => continue,
-        };
+/// Look up an element by its `id` attribute across the whole document.
+fn find_by_id<'a>(document: &'a Html, id: &str) -> Option<scraper::ElementRef<'a>> {
+    let selector = Selector::parse("[id]").ok()?;
+    document
+        .select(&selector)
+        .find(|el| el.value().attr("id") == Some(id))
+}
 
-        // Get property value
-        let prop_value = if prop_element.value().attr("itemscope").is_some() {
-            // Nested item
-            extract_item(&prop_element)
-        } else {
-            // Scalar value - depends on element type
-            let tag = prop_element.value().name();
-            let value_string: String = match tag {
-                "meta" => prop_element.value().attr("content").unwrap_or("").to_string(),
-                "link" | "a" | "area" => prop_element.value().attr("href").unwrap_or("").to_string(),
-                "img" | "audio" | "video" | "source" => {
-                    prop_element.value().attr("src").unwrap_or("").to_string()
-                }
-                "time" => {
-                    prop_element
-                        .value()
-                        .attr("datetime")
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| prop_element.text().collect::<String>())
-                }
-                "data" | "meter" => prop_element.value().attr("value").unwrap_or("").to_string(),
-                _ => {
-                    // Use text content
-                    prop_element.text().collect::<String>()
-                }
-            };
-            Value::String(value_string.trim().to_string())
+/// Resolve a single itemprop element's value and fold it into `item`,
+/// accumulating repeated property names into an array.
+fn fold_prop(item: &mut Map<String, Value>, prop_element: &scraper::ElementRef, document: &Html) {
+    let prop_name = match prop_element.value().attr("itemprop") {
+        Some(name) => name.to_string(),
+        None => return,
+    };
+
+    let prop_value = if prop_element.value().attr("itemscope").is_some() {
+        // Nested item
+        extract_item(prop_element, document)
+    } else {
+        // Scalar value - depends on element type
+        let tag = prop_element.value().name();
+        let value_string: String = match tag {
+            "meta" => prop_element.value().attr("content").unwrap_or("").to_string(),
+            "link" | "a" | "area" => prop_element.value().attr("href").unwrap_or("").to_string(),
+            "img" | "audio" | "video" | "source" => {
+                prop_element.value().attr("src").unwrap_or("").to_string()
+            }
+            "time" => {
+                prop_element
+                    .value()
+                    .attr("datetime")
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| prop_element.text().collect::<String>())
+            }
+            "data" | "meter" => prop_element.value().attr("value").unwrap_or("").to_string(),
+            _ => {
+                // Use text content
+                prop_element.text().collect::<String>()
+            }
         };
+        Value::String(value_string.trim().to_string())
+    };
 
-        // Handle multiple values for same property
-        if let Some(existing) = item.get_mut(&prop_name) {
-            match existing {
-                Value::Array(arr) => {
-                    arr.push(prop_value);
-                }
-                _ => {
-                    let old = existing.clone();
-                    *existing = Value::Array(vec![old, prop_value]);
-                }
+    // Handle multiple values for same property
+    if let Some(existing) = item.get_mut(&prop_name) {
+        match existing {
+            Value::Array(arr) => {
+                arr.push(prop_value);
+            }
+            _ => {
+                let old = existing.clone();
+                *existing = Value::Array(vec![old, prop_value]);
             }
-        } else {
-            item.insert(prop_name, prop_value);
         }
+    } else {
+        item.insert(prop_name, prop_value);
     }
-
-    Value::Object(item)
 }
 
 #[cfg(test)]
@@ -198,5 +244,31 @@ mod tests {
         let product = &result["Product"];
         assert!(product.get("offers").is_some());
         assert_eq!(product["offers"]["price"].as_str().unwrap(), "19.99");
+        // The nested Offer's price must not also leak onto the parent Product.
+        assert!(product.get("price").is_none());
+    }
+
+    #[test]
+    fn test_itemref_microdata() {
+        // Price and availability are split into a referenced block outside the
+        // itemscope subtree, as many real Product/Offer markups do.
+        let html = r#"
+        <div itemscope itemtype="https://schema.org/Product" itemref="offer-details">
+            <span itemprop="name">Product</span>
+        </div>
+        <div id="offer-details">
+            <span itemprop="price">19.99</span>
+            <link itemprop="availability" href="https://schema.org/InStock">
+        </div>
+        "#;
+
+        let result = extract_microdata(html);
+        let product = &result["Product"];
+        assert_eq!(product["name"].as_str().unwrap(), "Product");
+        assert_eq!(product["price"].as_str().unwrap(), "19.99");
+        assert_eq!(
+            product["availability"].as_str().unwrap(),
+            "https://schema.org/InStock"
+        );
     }
 }
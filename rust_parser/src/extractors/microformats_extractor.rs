@@ -0,0 +1,283 @@
+//! microformats2 extraction
+//!
+//! Parses the mf2 class-name grammar: root `h-*` classes become typed objects
+//! and `p-*`/`u-*`/`dt-*`/`e-*` classes become properties, mirroring the shape
+//! of [`super::extract_microdata`].
+//! Reference: https://microformats.org/wiki/microformats2
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+
+/// Extract microformats2 data from HTML, keyed by root `h-*` type
+pub fn extract_microformats(html: &str) -> Value {
+    let document = Html::parse_document(html);
+
+    let selector = match Selector::parse("[class]") {
+        Ok(s) => s,
+        Err(_) => return Value::Object(Map::new()),
+    };
+
+    let mut result: Map<String, Value> = Map::new();
+
+    for element in document.select(&selector) {
+        if root_types(&element).is_empty() {
+            continue;
+        }
+
+        // Skip roots nested inside another root (handled as a property).
+        let is_nested = element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|el| !root_types(&el).is_empty());
+        if is_nested {
+            continue;
+        }
+
+        let item = extract_mf2_item(&element);
+        for type_name in root_types(&element) {
+            match result.get_mut(&type_name) {
+                Some(Value::Array(arr)) => arr.push(item.clone()),
+                Some(existing) => {
+                    let old = existing.clone();
+                    *existing = Value::Array(vec![old, item.clone()]);
+                }
+                None => {
+                    result.insert(type_name, item.clone());
+                }
+            }
+        }
+    }
+
+    Value::Object(result)
+}
+
+/// Root `h-*` class names on an element (e.g. `h-card`, `h-entry`).
+fn root_types(element: &ElementRef) -> Vec<String> {
+    class_list(element)
+        .filter(|c| c.starts_with("h-"))
+        .map(|c| c.to_string())
+        .collect()
+}
+
+fn class_list<'a>(element: &'a ElementRef) -> impl Iterator<Item = &'a str> {
+    element
+        .value()
+        .attr("class")
+        .unwrap_or("")
+        .split_whitespace()
+}
+
+fn extract_mf2_item(root: &ElementRef) -> Value {
+    let mut item: Map<String, Value> = Map::new();
+
+    collect_properties(root, root, &mut item);
+
+    Value::Object(item)
+}
+
+/// Walk the children of `current`, folding property classes into `item`. A
+/// property child that is also a root `h-*` becomes a nested object; a bare
+/// nested root without a property class is skipped; plain children are
+/// descended into so nested property markup is reached.
+fn collect_properties(root: &ElementRef, current: &ElementRef, item: &mut Map<String, Value>) {
+    for child in current.children().filter_map(ElementRef::wrap) {
+        let classes: Vec<String> = class_list(&child).map(|c| c.to_string()).collect();
+        let prop_names: Vec<&String> = classes
+            .iter()
+            .filter(|c| {
+                c.starts_with("p-")
+                    || c.starts_with("u-")
+                    || c.starts_with("dt-")
+                    || c.starts_with("e-")
+            })
+            .collect();
+        let is_root = classes.iter().any(|c| c.starts_with("h-"));
+
+        if !prop_names.is_empty() {
+            let value = if is_root {
+                // A property that is also a nested microformat.
+                extract_mf2_item(&child)
+            } else {
+                Value::String(property_value(&child, prop_names[0]))
+            };
+            for prop in &prop_names {
+                let name = property_name(prop);
+                insert_prop(item, name, value.clone());
+            }
+            // Don't descend into a consumed property subtree.
+            continue;
+        }
+
+        if is_root {
+            // A bare nested root with no property class is not a property of
+            // this item; leave it for the top-level scan.
+            continue;
+        }
+
+        // Plain wrapper element: descend to reach deeper property markup.
+        collect_properties(root, &child, item);
+    }
+}
+
+/// Strip the `p-`/`u-`/`dt-`/`e-` prefix from a property class name.
+fn property_name(class: &str) -> String {
+    class
+        .split_once('-')
+        .map(|(_, rest)| rest.to_string())
+        .unwrap_or_else(|| class.to_string())
+}
+
+/// Resolve a property value according to its prefix and the mf2 value rules,
+/// including the `value-title` and `value-class` patterns.
+fn property_value(element: &ElementRef, class: &str) -> String {
+    // value-title: a descendant carrying its canonical value in `title`.
+    if let Some(vt) = find_value_title(element) {
+        return vt;
+    }
+    // value-class: concatenate the text of descendant `.value` elements.
+    let value_class = collect_value_class(element);
+    if let Some(vc) = value_class {
+        return vc;
+    }
+
+    if class.starts_with("u-") {
+        let tag = element.value().name();
+        let attr = match tag {
+            "a" | "area" | "link" => "href",
+            "img" | "audio" | "video" | "source" => "src",
+            "object" => "data",
+            _ => "",
+        };
+        if !attr.is_empty() {
+            if let Some(v) = element.value().attr(attr) {
+                return v.trim().to_string();
+            }
+        }
+        return element.text().collect::<String>().trim().to_string();
+    }
+
+    if class.starts_with("dt-") {
+        if let Some(dt) = element.value().attr("datetime") {
+            return dt.trim().to_string();
+        }
+        return element.text().collect::<String>().trim().to_string();
+    }
+
+    if class.starts_with("e-") {
+        return element.inner_html().trim().to_string();
+    }
+
+    // p-*: prefer alt/title/value on the relevant tags, else text.
+    let tag = element.value().name();
+    match tag {
+        "abbr" | "link" => element
+            .value()
+            .attr("title")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| element.text().collect::<String>().trim().to_string()),
+        "img" | "area" => element
+            .value()
+            .attr("alt")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+        "data" | "input" => element
+            .value()
+            .attr("value")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| element.text().collect::<String>().trim().to_string()),
+        _ => element.text().collect::<String>().trim().to_string(),
+    }
+}
+
+/// `value-title` pattern: return the `title` of the first descendant carrying
+/// the `value-title` class.
+fn find_value_title(element: &ElementRef) -> Option<String> {
+    let selector = Selector::parse(".value-title").ok()?;
+    element
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr("title").map(|s| s.trim().to_string()))
+}
+
+/// `value-class` pattern: concatenate the text of descendant `.value` elements.
+fn collect_value_class(element: &ElementRef) -> Option<String> {
+    let selector = Selector::parse(".value").ok()?;
+    let parts: Vec<String> = element
+        .select(&selector)
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(""))
+    }
+}
+
+fn insert_prop(item: &mut Map<String, Value>, name: String, value: Value) {
+    if let Some(existing) = item.get_mut(&name) {
+        match existing {
+            Value::Array(arr) => arr.push(value),
+            _ => {
+                let old = existing.clone();
+                *existing = Value::Array(vec![old, value]);
+            }
+        }
+    } else {
+        item.insert(name, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_h_card() {
+        let html = r#"
+        <div class="h-card">
+            <span class="p-name">Jane Doe</span>
+            <a class="u-url" href="https://jane.example/">home</a>
+        </div>
+        "#;
+
+        let result = extract_microformats(html);
+        let card = &result["h-card"];
+        assert_eq!(card["name"].as_str().unwrap(), "Jane Doe");
+        assert_eq!(card["url"].as_str().unwrap(), "https://jane.example/");
+    }
+
+    #[test]
+    fn test_value_title_pattern() {
+        let html = r#"
+        <div class="h-entry">
+            <span class="dt-published">
+                <span class="value-title" title="2024-01-02T10:00:00Z">Jan 2</span>
+            </span>
+        </div>
+        "#;
+
+        let result = extract_microformats(html);
+        assert_eq!(
+            result["h-entry"]["published"].as_str().unwrap(),
+            "2024-01-02T10:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_nested_microformat() {
+        let html = r#"
+        <div class="h-entry">
+            <span class="p-name">A post</span>
+            <div class="p-author h-card">
+                <span class="p-name">Jane</span>
+            </div>
+        </div>
+        "#;
+
+        let result = extract_microformats(html);
+        let entry = &result["h-entry"];
+        assert_eq!(entry["name"].as_str().unwrap(), "A post");
+        assert_eq!(entry["author"]["name"].as_str().unwrap(), "Jane");
+    }
+}
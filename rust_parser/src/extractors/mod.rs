@@ -6,13 +6,23 @@ mod css_extractor;
 mod js_extractor;
 mod jsonld_extractor;
 mod microdata_extractor;
+mod microformats_extractor;
 mod opengraph_extractor;
+mod page_metadata;
+mod rdfa_extractor;
+mod registry;
+mod rules;
 
 pub use css_extractor::*;
 pub use js_extractor::*;
 pub use jsonld_extractor::*;
 pub use microdata_extractor::*;
+pub use microformats_extractor::*;
 pub use opengraph_extractor::*;
+pub use page_metadata::*;
+pub use rdfa_extractor::*;
+pub use registry::*;
+pub use rules::*;
 
 use serde::{Deserialize, Serialize};
 
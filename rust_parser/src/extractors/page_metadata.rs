@@ -0,0 +1,237 @@
+//! Unified page-metadata resolver
+//!
+//! Runs the JSON-LD, OpenGraph/Twitter and microdata extractors (plus standard
+//! `<meta>`/`<title>` parsing) and merges their output into a single normalized
+//! object so callers get one stable schema regardless of which format a given
+//! site happens to expose.
+//!
+//! Fields are resolved with an explicit precedence order
+//! (JSON-LD > microdata > OpenGraph > Twitter > bare meta) and each resolved
+//! field records which source won under a `_source` sibling key.
+
+use scraper::{Html, Selector};
+use serde_json::{Map, Value};
+
+use super::{extract_jsonld, extract_microdata, extract_opengraph};
+
+/// Sources in descending precedence order.
+const JSONLD: &str = "jsonld";
+const MICRODATA: &str = "microdata";
+const OPENGRAPH: &str = "opengraph";
+const TWITTER: &str = "twitter";
+const META: &str = "meta";
+
+/// Extract and merge page metadata into a single normalized object.
+///
+/// The returned object holds the resolved `title`, `description`, `image`,
+/// `author`, `published`, and `price` fields. Alongside each resolved field a
+/// `<field>_source` key names the extractor the value came from.
+pub fn extract_page_metadata(html: &str) -> Value {
+    let document = Html::parse_document(html);
+
+    let jsonld = extract_jsonld(html);
+    let microdata = super::extract_microdata_doc(&document);
+    let og = extract_opengraph(html);
+
+    let mut result: Map<String, Value> = Map::new();
+
+    // Per-field candidates listed in descending precedence order. Each is
+    // evaluated up front; the first source that yields a value wins.
+    let fields: [(&str, Vec<(&str, Option<String>)>); 6] = [
+        (
+            "title",
+            vec![
+                (JSONLD, schema_field(&jsonld, &["name", "headline", "title"])),
+                (MICRODATA, schema_field(&microdata, &["name", "headline", "title"])),
+                (OPENGRAPH, og_field(&og, &["og", "title"])),
+                (TWITTER, og_field(&og, &["twitter", "title"])),
+                (META, document_title(&document)),
+            ],
+        ),
+        (
+            "description",
+            vec![
+                (JSONLD, schema_field(&jsonld, &["description"])),
+                (MICRODATA, schema_field(&microdata, &["description"])),
+                (OPENGRAPH, og_field(&og, &["og", "description"])),
+                (TWITTER, og_field(&og, &["twitter", "description"])),
+                (META, og_field(&og, &["meta", "description"])),
+            ],
+        ),
+        (
+            "image",
+            vec![
+                (JSONLD, schema_field(&jsonld, &["image", "thumbnailUrl", "logo"])),
+                (MICRODATA, schema_field(&microdata, &["image", "thumbnailUrl", "logo"])),
+                (OPENGRAPH, og_field(&og, &["og", "image"])),
+                (TWITTER, og_field(&og, &["twitter", "image"])),
+            ],
+        ),
+        (
+            "author",
+            vec![
+                (JSONLD, schema_field(&jsonld, &["author", "creator"])),
+                (MICRODATA, schema_field(&microdata, &["author", "creator"])),
+                (OPENGRAPH, og_field(&og, &["og", "article", "author"])),
+                (META, og_field(&og, &["meta", "author"])),
+            ],
+        ),
+        (
+            "published",
+            vec![
+                (JSONLD, schema_field(&jsonld, &["datePublished", "dateCreated"])),
+                (MICRODATA, schema_field(&microdata, &["datePublished", "dateCreated"])),
+                (OPENGRAPH, og_field(&og, &["og", "article", "published_time"])),
+            ],
+        ),
+        (
+            "price",
+            vec![
+                (JSONLD, schema_price(&jsonld)),
+                (MICRODATA, schema_price(&microdata)),
+                (OPENGRAPH, og_field(&og, &["og", "product", "price:amount"])),
+            ],
+        ),
+    ];
+
+    for (field, candidates) in fields {
+        for (source, value) in candidates {
+            if let Some(value) = value {
+                result.insert(field.to_string(), Value::String(value));
+                result.insert(format!("{field}_source"), Value::String(source.to_string()));
+                break;
+            }
+        }
+    }
+
+    Value::Object(result)
+}
+
+/// Resolve the first of `keys` found across any typed object in a
+/// JSON-LD/microdata result (both are keyed by `@type`).
+fn schema_field(data: &Value, keys: &[&str]) -> Option<String> {
+    let obj = data.as_object()?;
+    for bucket in obj.values() {
+        for item in bucket_objects(bucket) {
+            for key in keys {
+                if let Some(value) = item.get(*key) {
+                    if let Some(s) = coerce_str(value) {
+                        return Some(s);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Resolve a price from schema.org `offers` (or a bare `price` property).
+fn schema_price(data: &Value) -> Option<String> {
+    let obj = data.as_object()?;
+    for bucket in obj.values() {
+        for item in bucket_objects(bucket) {
+            if let Some(offers) = item.get("offers") {
+                for offer in bucket_objects(offers) {
+                    if let Some(price) = offer.get("price").and_then(coerce_str) {
+                        return Some(price);
+                    }
+                }
+            }
+            if let Some(price) = item.get("price").and_then(coerce_str) {
+                return Some(price);
+            }
+        }
+    }
+    None
+}
+
+/// Navigate nested objects in the OpenGraph result (e.g. `["og", "title"]`).
+fn og_field(og: &Value, path: &[&str]) -> Option<String> {
+    let mut current = og;
+    for key in path {
+        current = current.as_object()?.get(*key)?;
+    }
+    coerce_str(current)
+}
+
+/// Yield the object(s) held under a `@type` bucket, which may be a single
+/// object or an array of objects.
+fn bucket_objects(value: &Value) -> Vec<&Map<String, Value>> {
+    match value {
+        Value::Object(obj) => vec![obj],
+        Value::Array(arr) => arr.iter().filter_map(|v| v.as_object()).collect(),
+        _ => vec![],
+    }
+}
+
+/// Coerce a JSON value to a scalar string, unwrapping the common shapes that
+/// carry a usable value (`{"url": ...}`, `{"name": ...}`, `{"_value": ...}`,
+/// or the first element of an array).
+fn coerce_str(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Array(arr) => arr.iter().find_map(coerce_str),
+        Value::Object(obj) => ["url", "name", "@id", "_value"]
+            .iter()
+            .find_map(|k| obj.get(*k).and_then(coerce_str)),
+        _ => None,
+    }
+}
+
+/// Read the `<title>` element's text content.
+fn document_title(document: &Html) -> Option<String> {
+    let selector = Selector::parse("title").ok()?;
+    let title = document.select(&selector).next()?;
+    let text: String = title.text().collect::<String>().trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonld_wins_over_opengraph() {
+        let html = r#"
+        <html>
+        <head>
+            <title>Title Tag</title>
+            <meta property="og:title" content="OG Title">
+            <meta property="og:image" content="https://example.com/og.jpg">
+            <script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "Product",
+                "name": "LD Title",
+                "offers": {"@type": "Offer", "price": "42.00"}
+            }
+            </script>
+        </head>
+        </html>
+        "#;
+
+        let result = extract_page_metadata(html);
+        assert_eq!(result["title"].as_str().unwrap(), "LD Title");
+        assert_eq!(result["title_source"].as_str().unwrap(), "jsonld");
+        assert_eq!(result["price"].as_str().unwrap(), "42.00");
+        // image only present in OpenGraph, so it wins there
+        assert_eq!(result["image"].as_str().unwrap(), "https://example.com/og.jpg");
+        assert_eq!(result["image_source"].as_str().unwrap(), "opengraph");
+    }
+
+    #[test]
+    fn test_falls_back_to_title_tag() {
+        let html = r#"
+        <html><head><title>Just A Title</title></head></html>
+        "#;
+
+        let result = extract_page_metadata(html);
+        assert_eq!(result["title"].as_str().unwrap(), "Just A Title");
+        assert_eq!(result["title_source"].as_str().unwrap(), "meta");
+    }
+}
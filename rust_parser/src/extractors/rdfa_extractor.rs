@@ -0,0 +1,208 @@
+//! RDFa (RDF in attributes) extraction
+//!
+//! Extracts structured data from `vocab`/`typeof`/`property`/`rel`/`resource`/
+//! `content` attributes, mirroring the shape of [`super::extract_microdata`].
+//! Reference: https://www.w3.org/TR/rdfa-lite/
+
+use scraper::{Html, Selector};
+use serde_json::{Map, Value};
+
+/// Extract RDFa data from HTML, keyed by `typeof`
+pub fn extract_rdfa(html: &str) -> Value {
+    let document = Html::parse_document(html);
+
+    // Find all elements that open a typed resource.
+    let selector = match Selector::parse("[typeof]") {
+        Ok(s) => s,
+        Err(_) => return Value::Object(Map::new()),
+    };
+
+    let mut result: Map<String, Value> = Map::new();
+
+    for element in document.select(&selector) {
+        // Skip nested typed resources (handled as part of their parent).
+        let is_nested = element
+            .ancestors()
+            .filter_map(|n| n.value().as_element())
+            .any(|el| el.attr("typeof").is_some());
+
+        if is_nested {
+            continue;
+        }
+
+        let item = extract_resource(&element);
+        if let Some(type_name) = item.get("@type").and_then(|v| v.as_str()) {
+            let type_key = type_name.to_string();
+
+            if let Some(existing) = result.get_mut(&type_key) {
+                match existing {
+                    Value::Array(arr) => arr.push(item),
+                    _ => {
+                        let old = existing.clone();
+                        *existing = Value::Array(vec![old, item]);
+                    }
+                }
+            } else {
+                result.insert(type_key, item);
+            }
+        }
+    }
+
+    Value::Object(result)
+}
+
+fn extract_resource(element: &scraper::ElementRef) -> Value {
+    let mut item: Map<String, Value> = Map::new();
+
+    if let Some(typeof_attr) = element.value().attr("typeof") {
+        item.insert(
+            "@type".to_string(),
+            Value::String(local_name(typeof_attr).to_string()),
+        );
+    }
+
+    // `resource` or `about` identifies the subject.
+    if let Some(id) = element
+        .value()
+        .attr("resource")
+        .or_else(|| element.value().attr("about"))
+    {
+        item.insert("@id".to_string(), Value::String(id.to_string()));
+    }
+
+    let prop_selector = match Selector::parse("[property], [rel]") {
+        Ok(s) => s,
+        Err(_) => return Value::Object(item),
+    };
+
+    for prop_element in element.select(&prop_selector) {
+        // Skip properties that belong to a nested typed resource.
+        let mut found_typeof = false;
+        let mut current = prop_element.parent();
+        while let Some(parent_node) = current {
+            // Reached the boundary node itself: the prop is a direct
+            // descendant. Compare ego-tree node ids, not the HTML `id`
+            // attribute (which is `None` for most typed containers and would
+            // short-circuit at the first ancestor).
+            if parent_node.id() == element.id() {
+                break;
+            }
+            // Hit an interior typed resource first, so the prop belongs to it.
+            if let Some(parent_elem) = parent_node.value().as_element() {
+                if parent_elem.attr("typeof").is_some() {
+                    found_typeof = true;
+                    break;
+                }
+            }
+            current = parent_node.parent();
+        }
+        if found_typeof {
+            continue;
+        }
+
+        let prop_name = match prop_element
+            .value()
+            .attr("property")
+            .or_else(|| prop_element.value().attr("rel"))
+        {
+            Some(name) => local_name(name).to_string(),
+            None => continue,
+        };
+
+        let prop_value = if prop_element.value().attr("typeof").is_some() {
+            extract_resource(&prop_element)
+        } else {
+            Value::String(resolve_value(&prop_element))
+        };
+
+        if let Some(existing) = item.get_mut(&prop_name) {
+            match existing {
+                Value::Array(arr) => arr.push(prop_value),
+                _ => {
+                    let old = existing.clone();
+                    *existing = Value::Array(vec![old, prop_value]);
+                }
+            }
+        } else {
+            item.insert(prop_name, prop_value);
+        }
+    }
+
+    Value::Object(item)
+}
+
+/// Resolve a property value, preferring an explicit `content`/`resource`
+/// attribute and otherwise falling back to the element-type rules shared with
+/// microdata extraction.
+fn resolve_value(element: &scraper::ElementRef) -> String {
+    if let Some(content) = element.value().attr("content") {
+        return content.trim().to_string();
+    }
+    if let Some(resource) = element.value().attr("resource") {
+        return resource.trim().to_string();
+    }
+
+    let tag = element.value().name();
+    let value_string: String = match tag {
+        "meta" => element.value().attr("content").unwrap_or("").to_string(),
+        "link" | "a" | "area" => element.value().attr("href").unwrap_or("").to_string(),
+        "img" | "audio" | "video" | "source" => {
+            element.value().attr("src").unwrap_or("").to_string()
+        }
+        "time" => element
+            .value()
+            .attr("datetime")
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| element.text().collect::<String>()),
+        "data" | "meter" => element.value().attr("value").unwrap_or("").to_string(),
+        _ => element.text().collect::<String>(),
+    };
+    value_string.trim().to_string()
+}
+
+/// Reduce a CURIE or URI (`schema:Product`, `https://schema.org/Product`) to
+/// its local name.
+fn local_name(term: &str) -> &str {
+    let after_slash = term.rsplit(['/', '#']).next().unwrap_or(term);
+    after_slash.rsplit(':').next().unwrap_or(after_slash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_simple_rdfa() {
+        let html = r#"
+        <div vocab="https://schema.org/" typeof="Product">
+            <span property="name">Test Product</span>
+            <meta property="gtin13" content="1234567890123">
+            <a property="url" href="/p/123">link</a>
+        </div>
+        "#;
+
+        let result = extract_rdfa(html);
+        let product = &result["Product"];
+        assert_eq!(product["name"].as_str().unwrap(), "Test Product");
+        assert_eq!(product["gtin13"].as_str().unwrap(), "1234567890123");
+        assert_eq!(product["url"].as_str().unwrap(), "/p/123");
+    }
+
+    #[test]
+    fn test_nested_rdfa() {
+        let html = r#"
+        <div typeof="schema:Product">
+            <span property="name">Product</span>
+            <div property="offers" typeof="schema:Offer">
+                <span property="price">19.99</span>
+            </div>
+        </div>
+        "#;
+
+        let result = extract_rdfa(html);
+        let product = &result["Product"];
+        assert_eq!(product["offers"]["price"].as_str().unwrap(), "19.99");
+        // The nested Offer's price must not also leak onto the parent Product.
+        assert!(product.get("price").is_none());
+    }
+}
@@ -0,0 +1,131 @@
+//! Pluggable extractor trait and registry
+//!
+//! A yt-dlp-style design: each structured-data format (and, later, each
+//! site-specific scraper) implements [`Extractor`], and [`ExtractorRegistry`]
+//! parses the document once and dispatches to every matching extractor,
+//! merging their output under namespaced keys.
+
+use scraper::Html;
+use serde_json::{Map, Value};
+
+use super::{extract_js_variables_doc, extract_microdata_doc};
+
+/// A composable, optionally site-targeted structured-data extractor.
+pub trait Extractor {
+    /// Whether this extractor should run for the given URL and parsed document.
+    fn matches(&self, url: &str, html: &Html) -> bool;
+
+    /// Extract this extractor's data from the already-parsed document.
+    fn extract(&self, html: &Html) -> Value;
+
+    /// Stable name, used as the namespace key in merged output.
+    fn name(&self) -> &str;
+}
+
+/// Dispatches a single parsed document to all matching extractors.
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn Extractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            extractors: Vec::new(),
+        }
+    }
+
+    /// Create a registry preloaded with the built-in format extractors.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(JsVariableExtractor));
+        registry.register(Box::new(MicrodataExtractor));
+        registry
+    }
+
+    /// Add an extractor to the registry.
+    pub fn register(&mut self, extractor: Box<dyn Extractor>) {
+        self.extractors.push(extractor);
+    }
+
+    /// Parse `html` once and run every matching extractor, merging the results
+    /// into an object keyed by each extractor's name.
+    pub fn extract_all(&self, url: &str, html: &str) -> Value {
+        let document = Html::parse_document(html);
+
+        let mut result: Map<String, Value> = Map::new();
+        for extractor in &self.extractors {
+            if extractor.matches(url, &document) {
+                result.insert(extractor.name().to_string(), extractor.extract(&document));
+            }
+        }
+
+        Value::Object(result)
+    }
+}
+
+impl Default for ExtractorRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// JavaScript variable extractor (`<script>` declarations and assignments).
+pub struct JsVariableExtractor;
+
+impl Extractor for JsVariableExtractor {
+    fn matches(&self, _url: &str, _html: &Html) -> bool {
+        true
+    }
+
+    fn extract(&self, html: &Html) -> Value {
+        extract_js_variables_doc(html)
+    }
+
+    fn name(&self) -> &str {
+        "js"
+    }
+}
+
+/// Microdata (itemscope/itemprop) extractor.
+pub struct MicrodataExtractor;
+
+impl Extractor for MicrodataExtractor {
+    fn matches(&self, _url: &str, _html: &Html) -> bool {
+        true
+    }
+
+    fn extract(&self, html: &Html) -> Value {
+        extract_microdata_doc(html)
+    }
+
+    fn name(&self) -> &str {
+        "microdata"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_dispatch_and_namespacing() {
+        let html = r#"
+        <html><body>
+            <div itemscope itemtype="https://schema.org/Product">
+                <span itemprop="name">Widget</span>
+            </div>
+            <script>var data = { price: 5 };</script>
+        </body></html>
+        "#;
+
+        let registry = ExtractorRegistry::with_defaults();
+        let result = registry.extract_all("https://shop.example/p/1", html);
+
+        assert_eq!(
+            result["microdata"]["Product"]["name"].as_str().unwrap(),
+            "Widget"
+        );
+        assert_eq!(result["js"]["data"]["price"].as_i64().unwrap(), 5);
+    }
+}
@@ -0,0 +1,308 @@
+//! Declarative, user-supplied extraction rules
+//!
+//! Turns the extractor from a fixed heuristic into a configurable engine: a
+//! caller describes the data it wants with tree-sitter queries (against script
+//! content) and CSS selectors (against the DOM), the rules are compiled once,
+//! and compile errors surface as `Err` instead of a silent fallback.
+
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tree_sitter::{Node, Parser, Query, QueryCursor, StreamingIterator};
+
+use super::node_to_value;
+
+/// A single declarative extraction rule, as supplied by the user.
+///
+/// Exactly one of `tree_sitter_query` or `css_selector` must be set.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleSpec {
+    /// Key the extracted value is stored under in the result.
+    pub name: String,
+    /// A tree-sitter query run against each `<script>`'s content.
+    #[serde(default)]
+    pub tree_sitter_query: Option<String>,
+    /// A CSS selector run against the DOM.
+    #[serde(default)]
+    pub css_selector: Option<String>,
+    /// For CSS rules, the attribute to read instead of the element text.
+    #[serde(default)]
+    pub attribute: Option<String>,
+    /// Optional transform applied to each value: `trim`, `number`, `json`.
+    #[serde(default)]
+    pub transform: Option<String>,
+}
+
+/// A compiled rule ready to apply to a document.
+enum CompiledRule {
+    TreeSitter {
+        name: String,
+        query: Query,
+        transform: Option<String>,
+    },
+    Css {
+        name: String,
+        selector: Selector,
+        attribute: Option<String>,
+        transform: Option<String>,
+    },
+}
+
+/// A validated set of extraction rules.
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compile a set of rule specs, returning the first compile error as `Err`.
+    pub fn compile(specs: &[RuleSpec]) -> Result<Self, String> {
+        let language: tree_sitter::Language = tree_sitter_javascript::LANGUAGE.into();
+        let mut rules = Vec::with_capacity(specs.len());
+
+        for spec in specs {
+            match (&spec.tree_sitter_query, &spec.css_selector) {
+                (Some(query_str), None) => {
+                    let query = Query::new(&language, query_str).map_err(|e| {
+                        format!("rule `{}`: invalid tree-sitter query: {}", spec.name, e)
+                    })?;
+                    rules.push(CompiledRule::TreeSitter {
+                        name: spec.name.clone(),
+                        query,
+                        transform: spec.transform.clone(),
+                    });
+                }
+                (None, Some(selector_str)) => {
+                    let selector = Selector::parse(selector_str).map_err(|e| {
+                        format!("rule `{}`: invalid CSS selector: {:?}", spec.name, e)
+                    })?;
+                    rules.push(CompiledRule::Css {
+                        name: spec.name.clone(),
+                        selector,
+                        attribute: spec.attribute.clone(),
+                        transform: spec.transform.clone(),
+                    });
+                }
+                (Some(_), Some(_)) => {
+                    return Err(format!(
+                        "rule `{}`: set only one of tree_sitter_query or css_selector",
+                        spec.name
+                    ));
+                }
+                (None, None) => {
+                    return Err(format!(
+                        "rule `{}`: one of tree_sitter_query or css_selector is required",
+                        spec.name
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Apply every rule to `html`, merging the results into an object keyed by
+    /// each rule's `name`.
+    pub fn apply(&self, html: &str) -> Value {
+        let document = Html::parse_document(html);
+        let script_sources = collect_script_sources(&document);
+
+        let mut result: Map<String, Value> = Map::new();
+        for rule in &self.rules {
+            let value = match rule {
+                CompiledRule::TreeSitter { query, transform, .. } => {
+                    apply_tree_sitter(query, &script_sources, transform)
+                }
+                CompiledRule::Css {
+                    selector,
+                    attribute,
+                    transform,
+                    ..
+                } => apply_css(&document, selector, attribute.as_deref(), transform),
+            };
+            if let Some(value) = value {
+                result.insert(rule.name().to_string(), value);
+            }
+        }
+
+        Value::Object(result)
+    }
+}
+
+impl CompiledRule {
+    fn name(&self) -> &str {
+        match self {
+            CompiledRule::TreeSitter { name, .. } | CompiledRule::Css { name, .. } => name,
+        }
+    }
+}
+
+/// Collect the text content of every `<script>` element.
+fn collect_script_sources(document: &Html) -> Vec<String> {
+    let selector = match Selector::parse("script") {
+        Ok(s) => s,
+        Err(_) => return Vec::new(),
+    };
+    document
+        .select(&selector)
+        .map(|el| el.inner_html())
+        .filter(|s| !s.trim().is_empty())
+        .collect()
+}
+
+/// Run a compiled tree-sitter query over each script, collecting one value per
+/// match: its first object/array/call capture, else its first capture.
+fn apply_tree_sitter(
+    query: &Query,
+    scripts: &[String],
+    transform: &Option<String>,
+) -> Option<Value> {
+    let mut parser = Parser::new();
+    let language: tree_sitter::Language = tree_sitter_javascript::LANGUAGE.into();
+    if parser.set_language(&language).is_err() {
+        return None;
+    }
+
+    let mut values: Vec<Value> = Vec::new();
+    for source in scripts {
+        let tree = match parser.parse(source, None) {
+            Some(t) => t,
+            None => continue,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+        while let Some(m) = matches.next() {
+            // Prefer a structured capture (object/array/JSON.parse) if the match
+            // has one, otherwise fall back to the first capture.
+            let capture = m
+                .captures
+                .iter()
+                .find(|c| matches!(c.node.kind(), "object" | "array" | "call_expression"))
+                .or_else(|| m.captures.first());
+            if let Some(capture) = capture {
+                if let Some(value) = capture_value(capture.node, source) {
+                    values.push(apply_transform(value, transform));
+                }
+            }
+        }
+    }
+
+    collapse(values)
+}
+
+/// Turn a captured node into a value: structured when it's an object/array/
+/// JSON.parse call, otherwise the raw source text.
+fn capture_value(node: Node, source: &str) -> Option<Value> {
+    match node.kind() {
+        "object" | "array" | "call_expression" => node_to_value(node, source),
+        _ => Some(Value::String(source[node.byte_range()].to_string())),
+    }
+}
+
+/// Apply a CSS rule, reading either an attribute or the element text.
+fn apply_css(
+    document: &Html,
+    selector: &Selector,
+    attribute: Option<&str>,
+    transform: &Option<String>,
+) -> Option<Value> {
+    let values: Vec<Value> = document
+        .select(selector)
+        .filter_map(|el| match attribute {
+            Some(attr) => el.value().attr(attr).map(|v| v.to_string()),
+            None => Some(el.text().collect::<String>().trim().to_string()),
+        })
+        .map(|s| apply_transform(Value::String(s), transform))
+        .collect();
+
+    collapse(values)
+}
+
+/// Apply an optional value transform.
+fn apply_transform(value: Value, transform: &Option<String>) -> Value {
+    let name = match transform {
+        Some(t) => t.as_str(),
+        None => return value,
+    };
+    match name {
+        "trim" => match value {
+            Value::String(s) => Value::String(s.trim().to_string()),
+            other => other,
+        },
+        "number" => value
+            .as_str()
+            .and_then(|s| s.trim().parse::<f64>().ok())
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(value),
+        "json" => value
+            .as_str()
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or(value),
+        _ => value,
+    }
+}
+
+/// Collapse a result list: `None` when empty, the single value when there is
+/// one, otherwise an array.
+fn collapse(mut values: Vec<Value>) -> Option<Value> {
+    match values.len() {
+        0 => None,
+        1 => Some(values.remove(0)),
+        _ => Some(Value::Array(values)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_rule_attribute() {
+        let specs = vec![RuleSpec {
+            name: "product".to_string(),
+            tree_sitter_query: None,
+            css_selector: Some("[data-product-json]".to_string()),
+            attribute: Some("data-product-json".to_string()),
+            transform: Some("json".to_string()),
+        }];
+        let rules = RuleSet::compile(&specs).unwrap();
+
+        let html = r#"<div data-product-json='{"sku":"X1"}'></div>"#;
+        let result = rules.apply(html);
+        assert_eq!(result["product"]["sku"].as_str().unwrap(), "X1");
+    }
+
+    #[test]
+    fn test_tree_sitter_rule() {
+        let specs = vec![RuleSpec {
+            name: "state".to_string(),
+            tree_sitter_query: Some(
+                r#"(variable_declarator
+                    name: (identifier) @n
+                    value: (object) @state
+                    (#eq? @n "__PRELOADED_STATE__"))"#
+                    .to_string(),
+            ),
+            css_selector: None,
+            attribute: None,
+            transform: None,
+        }];
+        let rules = RuleSet::compile(&specs).unwrap();
+
+        let html = r#"<script>const __PRELOADED_STATE__ = { loaded: true };</script>"#;
+        let result = rules.apply(html);
+        assert_eq!(result["state"]["loaded"].as_bool().unwrap(), true);
+    }
+
+    #[test]
+    fn test_invalid_selector_is_error() {
+        let specs = vec![RuleSpec {
+            name: "bad".to_string(),
+            tree_sitter_query: None,
+            css_selector: Some(">>>".to_string()),
+            attribute: None,
+            transform: None,
+        }];
+        assert!(RuleSet::compile(&specs).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! RSS 2.0 and Atom 1.0 feed parsing
+//!
+//! Auto-detects the feed dialect from the root element (`<rss>` vs `<feed>`)
+//! and maps `<item>`/`<entry>` to a uniform [`FeedItem`]. Gated behind the
+//! optional `rss` cargo feature so the dependency is only pulled in when feed
+//! support is wanted.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Serialize;
+
+/// A single normalized feed entry, shared across RSS and Atom.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct FeedItem {
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub guid: Option<String>,
+    pub published: Option<String>,
+    pub updated: Option<String>,
+    pub author: Option<String>,
+    pub summary: Option<String>,
+    pub content: Option<String>,
+}
+
+/// Parsed feed: the normalized items plus any non-fatal parse errors.
+#[derive(Debug, Default, Serialize)]
+pub struct FeedResult {
+    pub items: Vec<FeedItem>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dialect {
+    Unknown,
+    Rss,
+    Atom,
+}
+
+/// Parse an RSS 2.0 or Atom 1.0 document, resolving relative Atom links
+/// against `base_url` when supplied.
+pub fn parse_feed(xml: &str, base_url: Option<&str>) -> FeedResult {
+    let mut result = FeedResult::default();
+
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let base = base_url.and_then(|b| url::Url::parse(b).ok());
+
+    let mut buf = Vec::new();
+    let mut dialect = Dialect::Unknown;
+    let mut current_tag = String::new();
+    let mut in_item = false;
+    let mut in_author = false;
+    let mut item = FeedItem::default();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                current_tag = tag.clone();
+
+                // Detect the dialect from the first root element we see.
+                if dialect == Dialect::Unknown {
+                    match tag.as_str() {
+                        "rss" | "rdf:RDF" => dialect = Dialect::Rss,
+                        "feed" => dialect = Dialect::Atom,
+                        _ => {}
+                    }
+                }
+
+                match tag.as_str() {
+                    "item" | "entry" => {
+                        in_item = true;
+                        item = FeedItem::default();
+                    }
+                    "author" => in_author = true,
+                    // Atom links carry their target in attributes; honor the
+                    // alternate relation and resolve it against the base URL.
+                    "link" if in_item && dialect == Dialect::Atom => {
+                        let mut rel = None;
+                        let mut href = None;
+                        for attr in e.attributes().flatten() {
+                            let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                            let val = attr.unescape_value().unwrap_or_default().to_string();
+                            match key.as_str() {
+                                "rel" => rel = Some(val),
+                                "href" => href = Some(val),
+                                _ => {}
+                            }
+                        }
+                        if rel.as_deref().unwrap_or("alternate") == "alternate" {
+                            if let Some(href) = href {
+                                item.link = Some(resolve(base.as_ref(), &href));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) => {
+                let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                match tag.as_str() {
+                    "item" | "entry" if in_item => {
+                        result.items.push(std::mem::take(&mut item));
+                        in_item = false;
+                    }
+                    "author" => in_author = false,
+                    _ => {}
+                }
+                current_tag.clear();
+            }
+            Ok(ev @ (Event::Text(_) | Event::CData(_))) => {
+                if !in_item {
+                    continue;
+                }
+                // Text nodes are unescaped; CDATA sections are taken verbatim.
+                let text = match ev {
+                    Event::Text(e) => e.unescape().unwrap_or_default().to_string(),
+                    Event::CData(e) => String::from_utf8_lossy(&e).to_string(),
+                    _ => continue,
+                };
+                if text.trim().is_empty() {
+                    continue;
+                }
+
+                match current_tag.as_str() {
+                    "title" => item.title = Some(text),
+                    // RSS <link> holds the URL as text; Atom handled via attrs.
+                    "link" if dialect == Dialect::Rss => item.link = Some(text),
+                    "guid" | "id" => item.guid = Some(text),
+                    "pubDate" | "published" => item.published = Some(text),
+                    "updated" | "lastBuildDate" => item.updated = Some(text),
+                    "description" | "summary" => item.summary = Some(text),
+                    "content" | "content:encoded" => item.content = Some(text),
+                    // RSS uses <author>/<dc:creator>; Atom nests <name>.
+                    "author" | "dc:creator" => item.author = Some(text),
+                    "name" if in_author => item.author = Some(text),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                result.errors.push(format!("XML parse error: {}", e));
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    result
+}
+
+/// Resolve `href` against an optional base URL, falling back to the raw value.
+fn resolve(base: Option<&url::Url>, href: &str) -> String {
+    match base {
+        Some(base) => base
+            .join(href)
+            .map(|u| u.to_string())
+            .unwrap_or_else(|_| href.to_string()),
+        None => href.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+        <rss version="2.0">
+        <channel>
+            <title>Feed</title>
+            <item>
+                <title>First</title>
+                <link>https://example.com/1</link>
+                <guid>tag:1</guid>
+                <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+                <description>Summary one</description>
+            </item>
+        </channel>
+        </rss>"#;
+
+        let result = parse_feed(xml, None);
+        assert_eq!(result.items.len(), 1);
+        let item = &result.items[0];
+        assert_eq!(item.title.as_deref(), Some("First"));
+        assert_eq!(item.link.as_deref(), Some("https://example.com/1"));
+        assert_eq!(item.guid.as_deref(), Some("tag:1"));
+        assert_eq!(item.summary.as_deref(), Some("Summary one"));
+    }
+
+    #[test]
+    fn test_parse_atom_relative_link() {
+        let xml = r#"<?xml version="1.0"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed</title>
+            <entry>
+                <title>Post</title>
+                <id>urn:uuid:1</id>
+                <updated>2024-01-02T10:00:00Z</updated>
+                <link rel="alternate" href="/posts/1"/>
+                <author><name>Jane</name></author>
+                <summary>Hi</summary>
+            </entry>
+        </feed>"#;
+
+        let result = parse_feed(xml, Some("https://blog.example/feed.xml"));
+        let item = &result.items[0];
+        assert_eq!(item.title.as_deref(), Some("Post"));
+        assert_eq!(item.guid.as_deref(), Some("urn:uuid:1"));
+        assert_eq!(item.link.as_deref(), Some("https://blog.example/posts/1"));
+        assert_eq!(item.author.as_deref(), Some("Jane"));
+    }
+}
@@ -208,17 +208,160 @@ pub unsafe extern "C" fn extract_opengraph_ffi(
     }
 }
 
-/// Extract JS variables from HTML (placeholder - needs tree-sitter)
+/// Extract JSON state embedded in inline `<script>` tags.
+///
+/// Returns a JSON object mapping each detected bootstrap global (e.g.
+/// `__INITIAL_STATE__`, `__NUXT__`) or `application/json` script `id` to its
+/// parsed value. See [`extract_js_state`].
 #[no_mangle]
 pub unsafe extern "C" fn extract_js_ffi(
-    _html_ptr: *const c_char,
-    _html_len: usize,
+    html_ptr: *const c_char,
+    html_len: usize,
 ) -> ExtractionResultFFI {
-    // TODO: Implement JS extraction with tree-sitter
-    ExtractionResultFFI {
-        json_ptr: string_to_ptr("{}".to_string()),
-        error_ptr: ptr::null_mut(),
+    let html = match std::str::from_utf8(std::slice::from_raw_parts(html_ptr as *const u8, html_len)) {
+        Ok(s) => s,
+        Err(e) => {
+            return ExtractionResultFFI {
+                json_ptr: ptr::null_mut(),
+                error_ptr: string_to_ptr(format!("Invalid UTF-8: {}", e)),
+            };
+        }
+    };
+
+    let state = extract_js_state(html);
+
+    match serde_json::to_string(&state) {
+        Ok(json) => ExtractionResultFFI {
+            json_ptr: string_to_ptr(json),
+            error_ptr: ptr::null_mut(),
+        },
+        Err(e) => ExtractionResultFFI {
+            json_ptr: ptr::null_mut(),
+            error_ptr: string_to_ptr(format!("Serialization error: {}", e)),
+        },
+    }
+}
+
+/// Pull JSON state out of inline scripts without a full JS engine.
+///
+/// Each non-`src` `<script>` is scanned for assignments to common bootstrap
+/// globals (`window.__INITIAL_STATE__ = {...}`, `var __APOLLO_STATE__ = {...}`,
+/// `self.__next_f.push([...])`, ...); the value is delimited by balancing
+/// braces/brackets with string-literal awareness and parsed with `serde_json`.
+/// `application/json` scripts are keyed by their element `id`. Fragments that
+/// fail to parse are skipped.
+fn extract_js_state(html: &str) -> serde_json::Value {
+    use serde_json::{Map, Value};
+
+    let document = scraper::Html::parse_document(html);
+    let script_selector = match scraper::Selector::parse("script") {
+        Ok(s) => s,
+        Err(_) => return Value::Object(Map::new()),
+    };
+
+    // Anchor on an assignment (`=`) or a `.push(` whose target's final segment
+    // is a double-underscore bootstrap marker, so ordinary code isn't matched.
+    let anchor = regex::Regex::new(
+        r"(?:window\.|self\.|globalThis\.|var\s+|let\s+|const\s+)?((?:[A-Za-z_$][\w$]*\.)*__[\w$]+)\s*(=|\.push\s*\()",
+    );
+    let anchor = match anchor {
+        Ok(re) => re,
+        Err(_) => return Value::Object(Map::new()),
+    };
+
+    let mut out: Map<String, Value> = Map::new();
+
+    for element in document.select(&script_selector) {
+        if element.value().attr("src").is_some() {
+            continue;
+        }
+        let content = element.inner_html();
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        // `<script type="application/json" id="...">` blocks key on the id.
+        if element.value().attr("type") == Some("application/json") {
+            if let Some(id) = element.value().attr("id") {
+                if let Ok(value) = serde_json::from_str::<Value>(content.trim()) {
+                    out.insert(id.to_string(), value);
+                }
+                continue;
+            }
+        }
+
+        let bytes = content.as_bytes();
+        for caps in anchor.captures_iter(&content) {
+            let name = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+            let key = name.rsplit('.').next().unwrap_or(name).to_string();
+            let op = match caps.get(2) {
+                Some(m) => m,
+                None => continue,
+            };
+
+            // Find the opening brace/bracket after the operator.
+            let mut i = op.end();
+            while i < bytes.len() && bytes[i] != b'{' && bytes[i] != b'[' {
+                // Bail out if we run into a statement end before a value opens.
+                if bytes[i] == b';' || bytes[i] == b'\n' {
+                    break;
+                }
+                i += 1;
+            }
+            if i >= bytes.len() || (bytes[i] != b'{' && bytes[i] != b'[') {
+                continue;
+            }
+
+            if let Some(end) = balance_value(bytes, i) {
+                if let Ok(value) = serde_json::from_str::<Value>(&content[i..end]) {
+                    out.insert(key, value);
+                }
+            }
+        }
+    }
+
+    Value::Object(out)
+}
+
+/// Return the index just past the brace/bracket that closes the one at `start`,
+/// skipping over string literals (single/double/backtick) and escapes.
+fn balance_value(bytes: &[u8], start: usize) -> Option<usize> {
+    if bytes[start] != b'{' && bytes[start] != b'[' {
+        return None;
+    }
+
+    let mut depth = 0i32;
+    let mut in_string: Option<u8> = None;
+    let mut escaped = false;
+
+    let mut i = start;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if c == b'\\' {
+                escaped = true;
+            } else if c == quote {
+                in_string = None;
+            }
+        } else {
+            match c {
+                b'"' | b'\'' | b'`' => in_string = Some(c),
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+        i += 1;
     }
+
+    None
 }
 
 /// Extract elements matching CSS selector
@@ -276,13 +419,62 @@ pub unsafe extern "C" fn extract_css_ffi(
     }
 }
 
+/// Extract site-tailored structured data, dispatching on URL
+///
+/// Consults the site-specific extractor registry and returns JSON from the
+/// highest-priority matching extractor, falling back to the generic
+/// JSON-LD/OpenGraph/meta extractor when no site handler claims the URL.
+///
+/// # Safety
+/// Same contract as [`extract_from_html`] for the HTML pointer; `url_ptr` must be
+/// a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn extract_site_ffi(
+    html_ptr: *const c_char,
+    html_len: usize,
+    url_ptr: *const c_char,
+) -> ExtractionResultFFI {
+    let html = match std::str::from_utf8(std::slice::from_raw_parts(html_ptr as *const u8, html_len)) {
+        Ok(s) => s,
+        Err(e) => {
+            return ExtractionResultFFI {
+                json_ptr: ptr::null_mut(),
+                error_ptr: string_to_ptr(format!("Invalid UTF-8 in HTML: {}", e)),
+            };
+        }
+    };
+
+    let url = match CStr::from_ptr(url_ptr).to_str() {
+        Ok(s) => s,
+        Err(e) => {
+            return ExtractionResultFFI {
+                json_ptr: ptr::null_mut(),
+                error_ptr: string_to_ptr(format!("Invalid URL: {}", e)),
+            };
+        }
+    };
+
+    let result = crate::extractors::extract_site(html, url);
+
+    match serde_json::to_string(&result) {
+        Ok(json) => ExtractionResultFFI {
+            json_ptr: string_to_ptr(json),
+            error_ptr: ptr::null_mut(),
+        },
+        Err(e) => ExtractionResultFFI {
+            json_ptr: ptr::null_mut(),
+            error_ptr: string_to_ptr(format!("Serialization error: {}", e)),
+        },
+    }
+}
+
 // ============================================================================
 // Batch Crawl + Extract (HTTP in Rust)
 // ============================================================================
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 
 /// Request for batch crawling
 #[derive(Debug, serde::Deserialize)]
@@ -296,8 +488,41 @@ struct BatchCrawlRequest {
     timeout_ms: u64,
     #[serde(default = "default_concurrency")]
     concurrency: usize,
+    #[serde(default = "default_requests_per_second")]
+    requests_per_second: f64, // Sustained token-bucket refill rate per domain
+    #[serde(default = "default_burst")]
+    burst: f64, // Token-bucket capacity per domain
+    #[serde(default = "default_per_domain_concurrency")]
+    per_domain_concurrency: usize, // Max in-flight requests per domain
+    #[serde(default)]
+    cache: HashMap<String, CacheEntry>, // Per-URL validators for conditional GETs
+    #[serde(default)]
+    max_retries: usize,
+    #[serde(default = "default_base_backoff")]
+    base_backoff_ms: u64,
+    #[serde(default)]
+    accept_invalid_certs: bool,
     #[serde(default)]
-    delay_ms: u64, // Min delay between requests to same domain
+    proxy: Option<String>,
+    #[serde(default = "default_max_redirects")]
+    max_redirects: usize,
+    #[serde(default)]
+    connect_timeout_ms: Option<u64>,
+}
+
+fn default_max_redirects() -> usize {
+    10
+}
+
+/// Cached response metadata for conditional fetching.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct CacheEntry {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    #[serde(default)]
+    body: String,
 }
 
 fn default_user_agent() -> String {
@@ -312,6 +537,14 @@ fn default_concurrency() -> usize {
     4
 }
 
+fn default_per_domain_concurrency() -> usize {
+    2
+}
+
+fn default_base_backoff() -> u64 {
+    500
+}
+
 /// Extract domain from URL
 fn extract_domain(url: &str) -> String {
     url::Url::parse(url)
@@ -320,8 +553,77 @@ fn extract_domain(url: &str) -> String {
         .unwrap_or_default()
 }
 
-/// Per-domain rate limiter
-type DomainRateLimiter = Arc<Mutex<HashMap<String, std::time::Instant>>>;
+/// Per-domain token-bucket state.
+struct DomainBucket {
+    tokens: f64,
+    last_refill: std::time::Instant,
+}
+
+/// Shared politeness limiter combining a per-domain token bucket (sustained
+/// `rate` with `burst` capacity) and a per-domain in-flight concurrency cap, so
+/// one slow host can't starve the others while global concurrency still holds.
+#[derive(Clone)]
+struct PolitenessLimiter {
+    buckets: Arc<Mutex<HashMap<String, DomainBucket>>>,
+    semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    rate: f64,
+    burst: f64,
+    per_domain_concurrency: usize,
+}
+
+impl PolitenessLimiter {
+    fn new(rate: f64, burst: f64, per_domain_concurrency: usize) -> Self {
+        Self {
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+            semaphores: Arc::new(Mutex::new(HashMap::new())),
+            rate: if rate > 0.0 { rate } else { 1.0 },
+            burst: burst.max(1.0),
+            per_domain_concurrency: per_domain_concurrency.max(1),
+        }
+    }
+
+    /// Acquire (creating if needed) the per-domain concurrency permit. Held for
+    /// the duration of the request by keeping the returned permit alive.
+    async fn acquire(&self, domain: &str) -> tokio::sync::OwnedSemaphorePermit {
+        let sem = {
+            let mut map = self.semaphores.lock().await;
+            map.entry(domain.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.per_domain_concurrency)))
+                .clone()
+        };
+        // A semaphore is never closed here, so acquire cannot fail.
+        sem.acquire_owned().await.expect("semaphore closed")
+    }
+
+    /// Block until a token is available for `domain`, refilling by elapsed time.
+    async fn throttle(&self, domain: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().await;
+                let bucket = buckets.entry(domain.to_string()).or_insert_with(|| DomainBucket {
+                    tokens: self.burst,
+                    last_refill: std::time::Instant::now(),
+                });
+
+                let elapsed = bucket.last_refill.elapsed().as_secs_f64();
+                bucket.last_refill = std::time::Instant::now();
+                bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    None
+                } else {
+                    Some((1.0 - bucket.tokens) / self.rate)
+                }
+            };
+
+            match wait {
+                Some(secs) => tokio::time::sleep(Duration::from_secs_f64(secs)).await,
+                None => return,
+            }
+        }
+    }
+}
 
 /// Single crawl result
 #[derive(Debug, serde::Serialize)]
@@ -333,6 +635,112 @@ struct CrawlResult {
     error: Option<String>,
     extracted: Option<serde_json::Value>,
     response_time_ms: u64,
+    /// Set when the server answered `304 Not Modified` and the cached body was reused.
+    not_modified: bool,
+    /// `ETag` header from a `200` response, for the caller to persist.
+    etag: Option<String>,
+    /// `Last-Modified` header from a `200` response, for the caller to persist.
+    last_modified: Option<String>,
+    /// Number of retries performed before this result was produced.
+    retries: usize,
+}
+
+/// Retry/backoff policy threaded into [`fetch_and_extract`].
+#[derive(Clone, Copy)]
+struct RetryPolicy {
+    max_retries: usize,
+    base_backoff_ms: u64,
+    timeout_ms: u64,
+}
+
+/// HTTP statuses worth retrying (transient upstream throttling / gateway).
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
+
+/// Whether a reqwest transport error is transient enough to retry.
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_request()
+}
+
+/// A jitter factor in `[0.5, 1.5)` derived from the wall clock, avoiding a
+/// dependency on a random-number generator.
+fn jitter_factor() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    0.5 + (nanos % 1000) as f64 / 1000.0
+}
+
+/// Exponential backoff with jitter: `base * 2^attempt * rand[0.5,1.5)`, capped.
+fn backoff_delay(base_backoff_ms: u64, attempt: usize, cap: Duration) -> Duration {
+    let factor = 2u64.saturating_pow(attempt as u32);
+    let ms = (base_backoff_ms.saturating_mul(factor)) as f64 * jitter_factor();
+    Duration::from_millis(ms as u64).min(cap)
+}
+
+/// Parse a `Retry-After` header, accepting both delta-seconds and HTTP-date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let target = parse_http_date(value)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some(Duration::from_secs(target.saturating_sub(now)))
+}
+
+/// Parse an IMF-fixdate (`Wed, 21 Oct 2015 07:28:00 GMT`) to epoch seconds.
+pub(crate) fn parse_http_date(value: &str) -> Option<u64> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() < 6 {
+        return None;
+    }
+    let day: i64 = parts[1].parse().ok()?;
+    let month = match parts[2] {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts[3].parse().ok()?;
+    let time: Vec<&str> = parts[4].split(':').collect();
+    if time.len() != 3 {
+        return None;
+    }
+    let (h, m, s): (i64, i64, i64) = (
+        time[0].parse().ok()?,
+        time[1].parse().ok()?,
+        time[2].parse().ok()?,
+    );
+
+    // Days since the Unix epoch (Howard Hinnant's days_from_civil).
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    let secs = days * 86400 + h * 3600 + m * 60 + s;
+    if secs < 0 {
+        None
+    } else {
+        Some(secs as u64)
+    }
 }
 
 /// Batch crawl response
@@ -346,62 +754,70 @@ async fn fetch_and_extract(
     client: &reqwest::Client,
     url: String,
     extraction: &Option<ExtractionRequest>,
-    rate_limiter: &DomainRateLimiter,
-    delay_ms: u64,
+    limiter: &PolitenessLimiter,
+    cache_entry: Option<CacheEntry>,
+    retry: RetryPolicy,
 ) -> CrawlResult {
     let start = std::time::Instant::now();
 
-    // Apply per-domain rate limiting
-    if delay_ms > 0 {
-        let domain = extract_domain(&url);
-        let delay = Duration::from_millis(delay_ms);
-
-        let wait_time = {
-            let limiter = rate_limiter.lock().await;
-            if let Some(last_access) = limiter.get(&domain) {
-                let elapsed = last_access.elapsed();
-                if elapsed < delay {
-                    Some(delay - elapsed)
-                } else {
-                    None
-                }
-            } else {
-                None
+    // Enforce per-domain concurrency and token-bucket pacing. The permit is
+    // held for the rest of this request.
+    let domain = extract_domain(&url);
+    let _permit = limiter.acquire(&domain).await;
+    limiter.throttle(&domain).await;
+
+    let timeout_cap = Duration::from_millis(retry.timeout_ms);
+    let mut attempt = 0usize;
+
+    loop {
+        // Attach conditional-request headers when we hold cached validators.
+        let mut request = client.get(&url);
+        if let Some(entry) = &cache_entry {
+            if let Some(etag) = &entry.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
             }
-        };
-
-        if let Some(wait) = wait_time {
-            tokio::time::sleep(wait).await;
-        }
-
-        // Update last access time
-        {
-            let mut limiter = rate_limiter.lock().await;
-            limiter.insert(domain, std::time::Instant::now());
         }
-    }
 
-    match client.get(&url).send().await {
-        Ok(response) => {
-            let status = response.status().as_u16() as i32;
-            let content_type = response
-                .headers()
-                .get("content-type")
-                .and_then(|v| v.to_str().ok())
-                .unwrap_or("")
-                .to_string();
-
-            match response.text().await {
-                Ok(body) => {
-                    let extracted = if let Some(req) = extraction {
-                        let result = extract_all(&body, req);
-                        // Convert HashMap to JSON Value
-                        serde_json::to_value(&result.values).ok()
-                    } else {
-                        None
-                    };
+        match request.send().await {
+            Ok(response) => {
+                let status_code = response.status().as_u16();
+
+                // Retry transient upstream throttling / gateway failures,
+                // honoring Retry-After when present.
+                if is_retryable_status(status_code) && attempt < retry.max_retries {
+                    let delay = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_retry_after)
+                        .map(|d| d.min(timeout_cap))
+                        .unwrap_or_else(|| {
+                            backoff_delay(retry.base_backoff_ms, attempt, timeout_cap)
+                        });
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
 
-                    CrawlResult {
+                let status = status_code as i32;
+                let content_type = response
+                    .headers()
+                    .get("content-type")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+
+                // 304: reuse the cached body without re-reading the response.
+                if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                    let body = cache_entry.map(|e| e.body).unwrap_or_default();
+                    let extracted = extraction
+                        .as_ref()
+                        .and_then(|req| serde_json::to_value(extract_all(&body, req).values).ok());
+
+                    return CrawlResult {
                         url,
                         status,
                         content_type,
@@ -409,28 +825,85 @@ async fn fetch_and_extract(
                         error: None,
                         extracted,
                         response_time_ms: start.elapsed().as_millis() as u64,
+                        not_modified: true,
+                        etag: None,
+                        last_modified: None,
+                        retries: attempt,
+                    };
+                }
+
+                let header_value = |name: reqwest::header::HeaderName| {
+                    response
+                        .headers()
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(|s| s.to_string())
+                };
+                let etag = header_value(reqwest::header::ETAG);
+                let last_modified = header_value(reqwest::header::LAST_MODIFIED);
+
+                return match response.text().await {
+                    Ok(body) => {
+                        let extracted = if let Some(req) = extraction {
+                            let result = extract_all(&body, req);
+                            // Convert HashMap to JSON Value
+                            serde_json::to_value(&result.values).ok()
+                        } else {
+                            None
+                        };
+
+                        CrawlResult {
+                            url,
+                            status,
+                            content_type,
+                            body,
+                            error: None,
+                            extracted,
+                            response_time_ms: start.elapsed().as_millis() as u64,
+                            not_modified: false,
+                            etag,
+                            last_modified,
+                            retries: attempt,
+                        }
                     }
+                    Err(e) => CrawlResult {
+                        url,
+                        status,
+                        content_type,
+                        body: String::new(),
+                        error: Some(format!("Body read error: {}", e)),
+                        extracted: None,
+                        response_time_ms: start.elapsed().as_millis() as u64,
+                        not_modified: false,
+                        etag: None,
+                        last_modified: None,
+                        retries: attempt,
+                    },
+                };
+            }
+            Err(e) => {
+                if is_retryable_error(&e) && attempt < retry.max_retries {
+                    let delay = backoff_delay(retry.base_backoff_ms, attempt, timeout_cap);
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    continue;
                 }
-                Err(e) => CrawlResult {
+
+                return CrawlResult {
                     url,
-                    status,
-                    content_type,
+                    status: 0,
+                    content_type: String::new(),
                     body: String::new(),
-                    error: Some(format!("Body read error: {}", e)),
+                    error: Some(e.to_string()),
                     extracted: None,
                     response_time_ms: start.elapsed().as_millis() as u64,
-                },
+                    not_modified: false,
+                    etag: None,
+                    last_modified: None,
+                    retries: attempt,
+                };
             }
         }
-        Err(e) => CrawlResult {
-            url,
-            status: 0,
-            content_type: String::new(),
-            body: String::new(),
-            error: Some(e.to_string()),
-            extracted: None,
-            response_time_ms: start.elapsed().as_millis() as u64,
-        },
     }
 }
 
@@ -466,11 +939,29 @@ pub unsafe extern "C" fn crawl_batch_ffi(
     };
 
     // Build HTTP client
-    let client = match reqwest::Client::builder()
+    let mut builder = reqwest::Client::builder()
         .user_agent(&request.user_agent)
         .timeout(Duration::from_millis(request.timeout_ms))
-        .build()
-    {
+        .danger_accept_invalid_certs(request.accept_invalid_certs)
+        .redirect(reqwest::redirect::Policy::limited(request.max_redirects));
+
+    if let Some(connect_timeout_ms) = request.connect_timeout_ms {
+        builder = builder.connect_timeout(Duration::from_millis(connect_timeout_ms));
+    }
+
+    if let Some(proxy_url) = &request.proxy {
+        match reqwest::Proxy::all(proxy_url) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => {
+                return ExtractionResultFFI {
+                    json_ptr: ptr::null_mut(),
+                    error_ptr: string_to_ptr(format!("Invalid proxy: {}", e)),
+                };
+            }
+        }
+    }
+
+    let client = match builder.build() {
         Ok(c) => c,
         Err(e) => {
             return ExtractionResultFFI {
@@ -496,15 +987,27 @@ pub unsafe extern "C" fn crawl_batch_ffi(
 
         let concurrency = request.concurrency.max(1).min(32);
         let extraction = request.extraction.clone();
-        let delay_ms = request.delay_ms;
-        let rate_limiter: DomainRateLimiter = Arc::new(Mutex::new(HashMap::new()));
+        let cache = request.cache.clone();
+        let limiter = PolitenessLimiter::new(
+            request.requests_per_second,
+            request.burst,
+            request.per_domain_concurrency,
+        );
+        let retry = RetryPolicy {
+            max_retries: request.max_retries,
+            base_backoff_ms: request.base_backoff_ms,
+            timeout_ms: request.timeout_ms,
+        };
 
         stream::iter(request.urls)
             .map(|url| {
                 let client = client.clone();
                 let extraction = extraction.clone();
-                let rate_limiter = rate_limiter.clone();
-                async move { fetch_and_extract(&client, url, &extraction, &rate_limiter, delay_ms).await }
+                let limiter = limiter.clone();
+                let cache_entry = cache.get(&url).cloned();
+                async move {
+                    fetch_and_extract(&client, url, &extraction, &limiter, cache_entry, retry).await
+                }
             })
             .buffer_unordered(concurrency)
             .collect::<Vec<_>>()
@@ -525,6 +1028,362 @@ pub unsafe extern "C" fn crawl_batch_ffi(
     }
 }
 
+// ============================================================================
+// Link checking
+// ============================================================================
+
+/// Reachability verdict for a single URL, as produced by [`check_links`].
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum LinkStatus {
+    /// A 2xx response.
+    Ok { url: String, code: u16 },
+    /// A 3xx response, with its `Location` target.
+    Redirect { url: String, target: String },
+    /// A 4xx/5xx response.
+    Broken { url: String, code: u16 },
+    /// The request never produced a response (DNS, TLS, timeout, ...).
+    Unreachable { url: String, error: String },
+}
+
+/// Request timeout applied to each link check.
+const LINK_CHECK_TIMEOUT_MS: u64 = 10_000;
+
+/// Maximum simultaneous requests issued to any single host.
+const PER_HOST_CONCURRENCY: usize = 2;
+
+/// Check a batch of URLs for reachability.
+///
+/// Each distinct URL is probed with a `HEAD` request (falling back to `GET`
+/// when the server answers `405 Method Not Allowed`). Requests run with up to
+/// `concurrency` in flight globally and at most [`PER_HOST_CONCURRENCY`] per
+/// host, so no single domain is hammered. Redirects are reported rather than
+/// followed.
+pub fn check_links(links: &[String], concurrency: usize) -> Vec<LinkStatus> {
+    // Deduplicate while preserving first-seen order.
+    let mut seen = HashSet::new();
+    let unique: Vec<String> = links
+        .iter()
+        .filter(|u| seen.insert((*u).clone()))
+        .cloned()
+        .collect();
+
+    let client = match reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(Duration::from_millis(LINK_CHECK_TIMEOUT_MS))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => return unreachable_all(unique, &e.to_string()),
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(r) => r,
+        Err(e) => return unreachable_all(unique, &e.to_string()),
+    };
+
+    runtime.block_on(async {
+        use futures::stream::{self, StreamExt};
+
+        let concurrency = concurrency.max(1).min(64);
+        let host_sems: Arc<Mutex<HashMap<String, Arc<Semaphore>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+
+        stream::iter(unique)
+            .map(|url| {
+                let client = client.clone();
+                let host_sems = host_sems.clone();
+                async move { check_one_link(&client, url, &host_sems).await }
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+    })
+}
+
+/// Map every URL to [`LinkStatus::Unreachable`] with a shared error.
+fn unreachable_all(urls: Vec<String>, error: &str) -> Vec<LinkStatus> {
+    urls.into_iter()
+        .map(|url| LinkStatus::Unreachable {
+            url,
+            error: error.to_string(),
+        })
+        .collect()
+}
+
+/// Probe a single URL, respecting the per-host concurrency cap.
+async fn check_one_link(
+    client: &reqwest::Client,
+    url: String,
+    host_sems: &Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+) -> LinkStatus {
+    let host = extract_domain(&url);
+    let sem = {
+        let mut map = host_sems.lock().await;
+        map.entry(host)
+            .or_insert_with(|| Arc::new(Semaphore::new(PER_HOST_CONCURRENCY)))
+            .clone()
+    };
+    let _permit = sem.acquire().await.ok();
+
+    let mut response = client.head(&url).send().await;
+    // Some servers reject HEAD; retry those with GET.
+    if let Ok(resp) = &response {
+        if resp.status().as_u16() == 405 {
+            response = client.get(&url).send().await;
+        }
+    }
+
+    match response {
+        Ok(resp) => {
+            let code = resp.status().as_u16();
+            if resp.status().is_redirection() {
+                let target = resp
+                    .headers()
+                    .get("location")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+                LinkStatus::Redirect { url, target }
+            } else if code >= 400 {
+                LinkStatus::Broken { url, code }
+            } else {
+                LinkStatus::Ok { url, code }
+            }
+        }
+        Err(e) => LinkStatus::Unreachable {
+            url,
+            error: e.to_string(),
+        },
+    }
+}
+
+// ============================================================================
+// Single-file archiving
+// ============================================================================
+
+/// Controls which asset classes [`embed_assets`] inlines.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct EmbedOptions {
+    /// Skip `<script src>` references.
+    #[serde(default)]
+    pub skip_js: bool,
+    /// Skip `<img src>` and image `url(...)` references.
+    #[serde(default)]
+    pub skip_images: bool,
+    /// Skip web-font `url(...)` references.
+    #[serde(default)]
+    pub skip_fonts: bool,
+}
+
+/// Inline a page's external assets, producing a self-contained document.
+///
+/// Every `<img src>`, `<link rel="stylesheet">`, `<script src>` and `url(...)`
+/// reference in an inline `<style>` block is resolved against `base_url`,
+/// fetched, and rewritten to a `data:<mime>;base64,...` URI. References that
+/// fail to fetch are left untouched, and classes disabled in `opts` are
+/// skipped.
+pub fn embed_assets(html: &str, base_url: &str, opts: &EmbedOptions) -> String {
+    let base = match url::Url::parse(base_url) {
+        Ok(u) => u,
+        Err(_) => return html.to_string(),
+    };
+
+    // Gather (original reference, absolute URL) pairs to inline.
+    let mut refs: Vec<(String, String)> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push_ref = |orig: &str, refs: &mut Vec<(String, String)>| {
+        let orig = orig.trim();
+        if orig.is_empty() || orig.starts_with("data:") {
+            return;
+        }
+        if let Ok(abs) = base.join(orig) {
+            if seen.insert(orig.to_string()) {
+                refs.push((orig.to_string(), abs.to_string()));
+            }
+        }
+    };
+
+    {
+        use scraper::{Html, Selector};
+        let document = Html::parse_document(html);
+
+        if !opts.skip_images {
+            if let Ok(sel) = Selector::parse("img[src]") {
+                for el in document.select(&sel) {
+                    if let Some(src) = el.value().attr("src") {
+                        push_ref(src, &mut refs);
+                    }
+                }
+            }
+        }
+        if let Ok(sel) = Selector::parse(r#"link[rel~="stylesheet"][href]"#) {
+            for el in document.select(&sel) {
+                if let Some(href) = el.value().attr("href") {
+                    push_ref(href, &mut refs);
+                }
+            }
+        }
+        if !opts.skip_js {
+            if let Ok(sel) = Selector::parse("script[src]") {
+                for el in document.select(&sel) {
+                    if let Some(src) = el.value().attr("src") {
+                        push_ref(src, &mut refs);
+                    }
+                }
+            }
+        }
+
+        // url(...) references inside inline <style> blocks.
+        if let Ok(sel) = Selector::parse("style") {
+            let url_re = regex::Regex::new(r#"url\(\s*['"]?([^'")]+)['"]?\s*\)"#).unwrap();
+            for el in document.select(&sel) {
+                let css = el.text().collect::<String>();
+                for cap in url_re.captures_iter(&css) {
+                    let target = &cap[1];
+                    if should_inline_css_url(target, opts) {
+                        push_ref(target, &mut refs);
+                    }
+                }
+            }
+        }
+    }
+
+    if refs.is_empty() {
+        return html.to_string();
+    }
+
+    // Fetch each asset concurrently and build data URIs.
+    let fetched = match fetch_data_uris(refs) {
+        Some(f) => f,
+        None => return html.to_string(),
+    };
+
+    // Substitute references in their quoted / url() contexts.
+    let mut out = html.to_string();
+    for (orig, data_uri) in fetched {
+        for wrap in [
+            format!("\"{}\"", orig),
+            format!("'{}'", orig),
+            format!("({})", orig),
+        ] {
+            let repl = wrap.replacen(&orig, &data_uri, 1);
+            out = out.replace(&wrap, &repl);
+        }
+    }
+    out
+}
+
+/// Whether a CSS `url(...)` target should be inlined under the given options.
+fn should_inline_css_url(target: &str, opts: &EmbedOptions) -> bool {
+    let lower = target.split(['?', '#']).next().unwrap_or(target).to_lowercase();
+    let is_font = [".woff2", ".woff", ".ttf", ".otf", ".eot"]
+        .iter()
+        .any(|e| lower.ends_with(e));
+    let is_image = [".png", ".jpg", ".jpeg", ".gif", ".webp", ".svg", ".ico", ".bmp"]
+        .iter()
+        .any(|e| lower.ends_with(e));
+
+    if is_font && opts.skip_fonts {
+        return false;
+    }
+    if is_image && opts.skip_images {
+        return false;
+    }
+    true
+}
+
+/// Fetch every (original, absolute) asset reference as a data URI.
+///
+/// Returns `None` only if the HTTP client or runtime can't be created.
+fn fetch_data_uris(refs: Vec<(String, String)>) -> Option<Vec<(String, String)>> {
+    let client = reqwest::Client::builder()
+        .user_agent(default_user_agent())
+        .timeout(Duration::from_secs(30))
+        .build()
+        .ok()?;
+    let runtime = tokio::runtime::Runtime::new().ok()?;
+
+    let results = runtime.block_on(async {
+        use futures::stream::{self, StreamExt};
+        stream::iter(refs)
+            .map(|(orig, abs)| {
+                let client = client.clone();
+                async move { fetch_one_data_uri(&client, &abs).await.map(|uri| (orig, uri)) }
+            })
+            .buffer_unordered(8)
+            .collect::<Vec<_>>()
+            .await
+    });
+
+    Some(results.into_iter().flatten().collect())
+}
+
+/// Fetch a single asset and encode it as a `data:` URI.
+async fn fetch_one_data_uri(client: &reqwest::Client, url: &str) -> Option<String> {
+    let response = client.get(url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let mime = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.split(';').next().unwrap_or(s).trim().to_string())
+        .unwrap_or_else(|| guess_mime(url));
+    let bytes = response.bytes().await.ok()?;
+    Some(format!("data:{};base64,{}", mime, base64_encode(&bytes)))
+}
+
+/// Best-effort MIME type from a URL's file extension.
+fn guess_mime(url: &str) -> String {
+    let lower = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    let mime = match lower.rsplit('.').next() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("css") => "text/css",
+        Some("js") => "application/javascript",
+        Some("woff2") => "font/woff2",
+        Some("woff") => "font/woff",
+        Some("ttf") => "font/ttf",
+        Some("otf") => "font/otf",
+        _ => "application/octet-stream",
+    };
+    mime.to_string()
+}
+
+/// Standard base64 encoding (RFC 4648) with padding.
+fn base64_encode(input: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((input.len() + 2) / 3 * 4);
+
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+
+        out.push(TABLE[((n >> 18) & 63) as usize] as char);
+        out.push(TABLE[((n >> 12) & 63) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            TABLE[((n >> 6) & 63) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            TABLE[(n & 63) as usize] as char
+        } else {
+            '='
+        });
+    }
+
+    out
+}
+
 // ============================================================================
 // Sitemap Fetching
 // ============================================================================
@@ -543,12 +1402,26 @@ struct SitemapRequest {
     timeout_ms: u64,
     #[serde(default)]
     discover_from_robots: bool,
+    #[serde(default = "default_requests_per_second")]
+    requests_per_second: f64,
+    #[serde(default = "default_burst")]
+    burst: f64,
+    #[serde(default = "default_true")]
+    respect_crawl_delay: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_requests_per_second() -> f64 {
+    1.0
+}
+
+fn default_burst() -> f64 {
+    1.0
+}
+
 fn default_max_depth() -> usize {
     5
 }
@@ -621,6 +1494,9 @@ unsafe fn fetch_sitemap_simple_inner(request_json: *const c_char) -> *mut c_char
             timeout_secs,
             request.recursive,
             request.max_depth,
+            request.requests_per_second,
+            request.burst,
+            request.respect_crawl_delay,
         );
         combined.urls.extend(result.urls);
         combined.sitemaps.extend(result.sitemaps);
@@ -667,6 +1543,94 @@ unsafe fn fetch_sitemap_ffi_inner(request_json: *const c_char) -> ExtractionResu
     }
 }
 
+// ============================================================================
+// Feed Fetching (RSS/Atom)
+// ============================================================================
+
+/// Request for feed fetching
+#[cfg(feature = "rss")]
+#[derive(Debug, serde::Deserialize)]
+struct FeedRequest {
+    url: String,
+    #[serde(default = "default_user_agent")]
+    user_agent: String,
+    #[serde(default = "default_timeout")]
+    timeout_ms: u64,
+}
+
+/// Parse an RSS/Atom document already held in memory.
+///
+/// # Returns
+/// JSON array of normalized feed items, or an `{"items":[],"errors":[...]}`
+/// object when the input is not UTF-8 or serialization fails (caller must free
+/// with free_rust_string)
+///
+/// # Safety
+/// Caller must ensure `html_ptr` points to valid UTF-8 of `html_len` bytes.
+#[cfg(feature = "rss")]
+#[no_mangle]
+pub unsafe extern "C" fn parse_feed_ffi(html_ptr: *const c_char, html_len: usize) -> *mut c_char {
+    let bytes = std::slice::from_raw_parts(html_ptr as *const u8, html_len);
+    let xml = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => return string_to_ptr(format!("{{\"items\":[],\"errors\":[\"Invalid UTF-8: {}\"]}}", e)),
+    };
+
+    let result = crate::feed::parse_feed(xml, None);
+    match serde_json::to_string(&result.items) {
+        Ok(json) => string_to_ptr(json),
+        Err(e) => string_to_ptr(format!("{{\"items\":[],\"errors\":[\"Serialization error: {}\"]}}", e)),
+    }
+}
+
+/// Fetch a URL and parse it as an RSS/Atom feed.
+///
+/// # Arguments
+/// * `request_json` - JSON FeedRequest
+///
+/// # Returns
+/// JSON FeedResult (caller must free with free_rust_string)
+#[cfg(feature = "rss")]
+#[no_mangle]
+pub unsafe extern "C" fn fetch_feed_simple(request_json: *const c_char) -> *mut c_char {
+    let request_str = match CStr::from_ptr(request_json).to_str() {
+        Ok(s) => s,
+        Err(e) => return string_to_ptr(format!("{{\"items\":[],\"errors\":[\"Invalid UTF-8: {}\"]}}", e)),
+    };
+
+    let request: FeedRequest = match serde_json::from_str(request_str) {
+        Ok(r) => r,
+        Err(e) => return string_to_ptr(format!("{{\"items\":[],\"errors\":[\"Invalid request: {}\"]}}", e)),
+    };
+
+    let timeout_secs = (request.timeout_ms / 1000).max(1);
+    let agent = ureq::Agent::new_with_config(
+        ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(timeout_secs)))
+            .user_agent(&request.user_agent)
+            .build(),
+    );
+
+    let result = match agent.get(&request.url).call() {
+        Ok(resp) => match resp.into_body().read_to_string() {
+            Ok(body) => crate::feed::parse_feed(&body, Some(&request.url)),
+            Err(e) => crate::feed::FeedResult {
+                items: vec![],
+                errors: vec![format!("Body read error: {}", e)],
+            },
+        },
+        Err(e) => crate::feed::FeedResult {
+            items: vec![],
+            errors: vec![format!("Fetch error: {}", e)],
+        },
+    };
+
+    match serde_json::to_string(&result) {
+        Ok(json) => string_to_ptr(json),
+        Err(e) => string_to_ptr(format!("{{\"items\":[],\"errors\":[\"Serialization error: {}\"]}}", e)),
+    }
+}
+
 // ============================================================================
 // Robots.txt Checking
 // ============================================================================
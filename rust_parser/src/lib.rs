@@ -10,6 +10,8 @@
 //! - Sitemap XML parsing
 
 mod extractors;
+#[cfg(feature = "rss")]
+pub mod feed;
 mod ffi;
 pub mod robots;
 pub mod sitemap;
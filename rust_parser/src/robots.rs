@@ -1,22 +1,53 @@
 //! robots.txt parsing and checking
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 use texting_robots::Robot;
 
-/// Cached robots.txt data per domain
+/// Cached robots.txt data per domain.
+///
+/// Entries are held in memory for the life of the process and mirrored to a
+/// per-domain JSON file under the OS cache directory so that short-lived
+/// extension invocations don't re-fetch robots.txt on every call.
 #[derive(Debug)]
 pub struct RobotsCache {
     cache: RwLock<HashMap<String, CachedRobots>>,
+    /// Directory the per-domain JSON files live in, if one could be resolved.
+    cache_dir: Option<PathBuf>,
 }
 
-#[derive(Debug)]
+/// A cached robots.txt entry, serialized verbatim to disk.
+///
+/// `fetched_at` is stored as wall-clock seconds since the Unix epoch rather
+/// than an [`std::time::Instant`] so that freshness survives a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct CachedRobots {
     /// Raw robots.txt content (Robot doesn't impl Clone, so we store raw)
     robots_txt: String,
     crawl_delay: Option<f64>,
     sitemaps: Vec<String>,
-    fetched_at: std::time::Instant,
+    /// Wall-clock fetch time, seconds since the Unix epoch.
+    fetched_at: u64,
+    /// `ETag` response header, for `If-None-Match` revalidation.
+    #[serde(default)]
+    etag: Option<String>,
+    /// `Last-Modified` response header, for `If-Modified-Since` revalidation.
+    #[serde(default)]
+    last_modified: Option<String>,
+    /// Seconds the entry stays fresh, from `Cache-Control`/`Expires` when the
+    /// server sends one, else [`DEFAULT_TTL_SECS`].
+    #[serde(default = "default_ttl")]
+    ttl_secs: u64,
+}
+
+/// Fallback freshness window when the server sends no caching directives.
+const DEFAULT_TTL_SECS: u64 = 3600;
+
+fn default_ttl() -> u64 {
+    DEFAULT_TTL_SECS
 }
 
 /// Result of robots.txt check
@@ -29,11 +60,77 @@ pub struct RobotsCheckResult {
 
 impl RobotsCache {
     pub fn new() -> Self {
+        let cache_dir = Self::resolve_cache_dir();
+        let cache = cache_dir
+            .as_ref()
+            .map(Self::load_from_disk)
+            .unwrap_or_default();
         Self {
-            cache: RwLock::new(HashMap::new()),
+            cache: RwLock::new(cache),
+            cache_dir,
         }
     }
 
+    /// Resolve `<os cache>/community-extensions/robots`, creating it if needed.
+    fn resolve_cache_dir() -> Option<PathBuf> {
+        let base = if cfg!(windows) {
+            std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+        } else if let Some(xdg) = std::env::var_os("XDG_CACHE_HOME") {
+            Some(PathBuf::from(xdg))
+        } else {
+            std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache"))
+        }?;
+
+        let dir = base.join("community-extensions").join("robots");
+        std::fs::create_dir_all(&dir).ok()?;
+        Some(dir)
+    }
+
+    /// Load every previously-persisted domain entry from `dir`.
+    fn load_from_disk(dir: &PathBuf) -> HashMap<String, CachedRobots> {
+        let mut map = HashMap::new();
+        let entries = match std::fs::read_dir(dir) {
+            Ok(e) => e,
+            Err(_) => return map,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let domain = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(d) => Self::unescape_domain(d),
+                None => continue,
+            };
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                if let Ok(cached) = serde_json::from_str::<CachedRobots>(&contents) {
+                    map.insert(domain, cached);
+                }
+            }
+        }
+        map
+    }
+
+    /// Persist a single domain entry, write-through, ignoring IO errors.
+    fn persist(&self, domain: &str, cached: &CachedRobots) {
+        if let Some(dir) = &self.cache_dir {
+            let path = dir.join(format!("{}.json", Self::escape_domain(domain)));
+            if let Ok(json) = serde_json::to_string(cached) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+
+    /// Map a domain to a filesystem-safe file stem (`:` in host:port etc.).
+    fn escape_domain(domain: &str) -> String {
+        domain.replace(['/', '\\', ':'], "_")
+    }
+
+    /// Inverse of [`Self::escape_domain`]; lossy but only used as a map key.
+    fn unescape_domain(stem: &str) -> String {
+        stem.to_string()
+    }
+
     /// Check if URL is allowed by robots.txt (using ureq)
     pub fn check_blocking(
         &self,
@@ -63,55 +160,105 @@ impl RobotsCache {
             }
         };
 
-        // Check cache first
-        {
-            if let Ok(cache) = self.cache.read() {
+        // Serve a still-fresh cache entry without touching the network.
+        let prior: Option<CachedRobots> = {
+            let cache = self.cache.read().ok();
+            if let Some(cache) = &cache {
                 if let Some(cached) = cache.get(&domain) {
-                    // Cache valid for 1 hour
-                    if cached.fetched_at.elapsed().as_secs() < 3600 {
+                    if now_secs().saturating_sub(cached.fetched_at) < cached.ttl_secs {
                         return Self::check_cached(cached, url, user_agent);
                     }
                 }
             }
-        }
+            cache.as_ref().and_then(|c| c.get(&domain).cloned())
+        };
 
-        // Fetch robots.txt
+        // Revalidate (or fetch fresh), sending conditional headers when we have
+        // a prior entry with validators.
         let robots_url = format!("{}://{}/robots.txt", parsed.scheme(), domain);
-        let robots_txt = match agent.get(&robots_url).call() {
+        let mut request = agent.get(&robots_url);
+        if let Some(prior) = &prior {
+            if let Some(etag) = &prior.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &prior.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+
+        let cached = match request.call() {
+            // 304 Not Modified: reuse the body, just refresh the timestamp.
+            Ok(resp) if resp.status().as_u16() == 304 && prior.is_some() => {
+                let mut refreshed = prior.unwrap();
+                refreshed.fetched_at = now_secs();
+                refreshed
+            }
             Ok(resp) if resp.status().is_success() => {
-                resp.into_body().read_to_string().unwrap_or_default()
+                let header = |name: &str| {
+                    resp.headers()
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(|s| s.to_string())
+                };
+                let etag = header("etag");
+                let last_modified = header("last-modified");
+                let ttl_secs = Self::cache_ttl(header("cache-control"), header("expires"))
+                    .unwrap_or(DEFAULT_TTL_SECS);
+                let robots_txt = resp.into_body().read_to_string().unwrap_or_default();
+
+                CachedRobots {
+                    crawl_delay: Self::extract_crawl_delay(&robots_txt, user_agent),
+                    sitemaps: Self::extract_sitemaps(&robots_txt),
+                    robots_txt,
+                    fetched_at: now_secs(),
+                    etag,
+                    last_modified,
+                    ttl_secs,
+                }
             }
-            _ => String::new(), // No robots.txt = allow all
+            _ => CachedRobots {
+                robots_txt: String::new(), // No robots.txt = allow all
+                crawl_delay: None,
+                sitemaps: vec![],
+                fetched_at: now_secs(),
+                etag: None,
+                last_modified: None,
+                ttl_secs: DEFAULT_TTL_SECS,
+            },
         };
 
-        // Parse robots.txt
-        let crawl_delay = Self::extract_crawl_delay(&robots_txt, user_agent);
-        let sitemaps = Self::extract_sitemaps(&robots_txt);
+        let result = Self::check_cached(&cached, url, user_agent);
 
-        // Check if allowed using Robot
-        let allowed = Robot::new(user_agent, robots_txt.as_bytes())
-            .map(|r| r.allowed(url))
-            .unwrap_or(true);
+        // Store in cache (in-memory and write-through to disk)
+        self.persist(&domain, &cached);
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(domain, cached);
+        }
 
-        let cached = CachedRobots {
-            robots_txt: robots_txt.clone(),
-            crawl_delay,
-            sitemaps: sitemaps.clone(),
-            fetched_at: std::time::Instant::now(),
-        };
+        result
+    }
 
-        // Store in cache
-        {
-            if let Ok(mut cache) = self.cache.write() {
-                cache.insert(domain, cached);
+    /// Derive a TTL from `Cache-Control: max-age` or `Expires`, if present.
+    fn cache_ttl(cache_control: Option<String>, expires: Option<String>) -> Option<u64> {
+        if let Some(cache_control) = cache_control {
+            for directive in cache_control.split(',') {
+                let directive = directive.trim().to_lowercase();
+                if let Some(max_age) = directive.strip_prefix("max-age=") {
+                    if let Ok(secs) = max_age.trim().parse::<u64>() {
+                        return Some(secs);
+                    }
+                }
             }
         }
 
-        RobotsCheckResult {
-            allowed,
-            crawl_delay,
-            sitemaps,
+        // Expires is an absolute HTTP-date; turn it into a relative TTL.
+        if let Some(expires) = expires {
+            if let Some(when) = crate::ffi::parse_http_date(&expires) {
+                return Some(when.saturating_sub(now_secs()));
+            }
         }
+
+        None
     }
 
     fn check_cached(cached: &CachedRobots, url: &str, user_agent: &str) -> RobotsCheckResult {
@@ -191,8 +338,134 @@ impl RobotsCache {
     }
 }
 
+/// Current wall-clock time as seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 impl Default for RobotsCache {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Fetches and expands sitemap documents discovered via robots.txt.
+///
+/// This is the natural complement to [`RobotsCache`]'s allow-check: given a
+/// site's `Sitemap:` URLs, it enumerates the leaf URLs, recursing through
+/// `<sitemapindex>` documents with a bounded depth and a visited-set to avoid
+/// cycles, and transparently decompressing gzip-compressed sitemaps.
+#[derive(Debug)]
+pub struct SitemapParser {
+    max_depth: usize,
+}
+
+impl SitemapParser {
+    pub fn new() -> Self {
+        Self { max_depth: 5 }
+    }
+
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self { max_depth }
+    }
+
+    /// Enumerate all leaf sitemap entries for `base_url`.
+    ///
+    /// The sitemap URLs are discovered from robots.txt, falling back to
+    /// `/sitemap.xml` at the host root when robots.txt lists none.
+    pub fn expand_sitemaps(
+        &self,
+        agent: &ureq::Agent,
+        base_url: &str,
+        user_agent: &str,
+    ) -> Vec<crate::sitemap::SitemapEntry> {
+        let robots = RobotsCache::new();
+        let mut sitemap_urls = robots.get_sitemaps_blocking(agent, base_url, user_agent);
+
+        if sitemap_urls.is_empty() {
+            if let Ok(parsed) = url::Url::parse(base_url) {
+                if let Some(host) = parsed.host_str() {
+                    sitemap_urls.push(format!("{}://{}/sitemap.xml", parsed.scheme(), host));
+                }
+            }
+        }
+
+        let mut visited = std::collections::HashSet::new();
+        let mut entries = Vec::new();
+        for sitemap_url in sitemap_urls {
+            self.fetch_recursive(agent, &sitemap_url, 0, &mut visited, &mut entries);
+        }
+        entries
+    }
+
+    /// Fetch one sitemap, appending its URLs and recursing into index children.
+    fn fetch_recursive(
+        &self,
+        agent: &ureq::Agent,
+        url: &str,
+        depth: usize,
+        visited: &mut std::collections::HashSet<String>,
+        entries: &mut Vec<crate::sitemap::SitemapEntry>,
+    ) {
+        if depth > self.max_depth {
+            return;
+        }
+        if !visited.insert(url.to_string()) {
+            return;
+        }
+
+        let body = match self.fetch_decoded(agent, url) {
+            Some(b) => b,
+            None => return,
+        };
+
+        let result = crate::sitemap::parse_sitemap(&body);
+        entries.extend(result.urls);
+        for child in result.sitemaps {
+            self.fetch_recursive(agent, &child.url, depth + 1, visited, entries);
+        }
+    }
+
+    /// Fetch a sitemap body, gunzipping when the response looks gzip-compressed.
+    fn fetch_decoded(&self, agent: &ureq::Agent, url: &str) -> Option<String> {
+        let resp = agent.get(url).call().ok()?;
+        if !resp.status().is_success() {
+            return None;
+        }
+
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_lowercase())
+        };
+        let content_encoding = header("content-encoding");
+        let content_type = header("content-type");
+
+        let bytes = resp.into_body().read_to_vec().ok()?;
+
+        let gz_suffix = url.split('?').next().unwrap_or(url).ends_with(".xml.gz");
+        let encoded_gzip = content_encoding.map(|e| e.contains("gzip")).unwrap_or(false);
+        let typed_gzip = content_type.map(|t| t.contains("gzip")).unwrap_or(false);
+        let magic_gzip = bytes.len() >= 2 && bytes[0] == 0x1f && bytes[1] == 0x8b;
+
+        if gz_suffix || encoded_gzip || typed_gzip || magic_gzip {
+            use std::io::Read;
+            let mut decoder = flate2::read::GzDecoder::new(&bytes[..]);
+            let mut text = String::new();
+            decoder.read_to_string(&mut text).ok()?;
+            Some(text)
+        } else {
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+    }
+}
+
+impl Default for SitemapParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,15 +1,104 @@
 //! Sitemap XML parsing
 
-use quick_xml::events::Event;
-use quick_xml::Reader;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::io::Cursor;
+use std::str::FromStr;
+
+/// Sitemaps.org namespace written on the root element by the writers; the
+/// parser matches element names only.
+const SITEMAP_NS: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// How frequently a page is likely to change, per the sitemaps.org schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    /// Lowercase token as written to `<changefreq>`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+impl FromStr for ChangeFreq {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "always" => Ok(ChangeFreq::Always),
+            "hourly" => Ok(ChangeFreq::Hourly),
+            "daily" => Ok(ChangeFreq::Daily),
+            "weekly" => Ok(ChangeFreq::Weekly),
+            "monthly" => Ok(ChangeFreq::Monthly),
+            "yearly" => Ok(ChangeFreq::Yearly),
+            "never" => Ok(ChangeFreq::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Image associated with a URL, from the Google image sitemap extension.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SitemapImage {
+    pub loc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+}
+
+/// Video associated with a URL, from the Google video sitemap extension.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SitemapVideo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_loc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_loc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+}
+
+/// News record associated with a URL, from the Google news sitemap extension.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SitemapNews {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
 
 /// Single sitemap entry
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SitemapEntry {
     pub url: String,
     pub lastmod: Option<String>,
-    pub changefreq: Option<String>,
+    pub changefreq: Option<ChangeFreq>,
     pub priority: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<SitemapImage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub videos: Vec<SitemapVideo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub news: Vec<SitemapNews>,
 }
 
 /// Sitemap index entry (references other sitemaps)
@@ -49,9 +138,16 @@ pub fn parse_sitemap(xml: &str) -> SitemapResult {
     // Current entry being built
     let mut url = String::new();
     let mut lastmod: Option<String> = None;
-    let mut changefreq: Option<String> = None;
+    let mut changefreq: Option<ChangeFreq> = None;
     let mut priority: Option<f64> = None;
 
+    // Accumulators for the image/video/news sitemap extensions. The
+    // namespaced child elements (e.g. `<image:image>`) appear inside an open
+    // `<url>`, so each URL accumulates its own media/news records.
+    let mut images: Vec<SitemapImage> = Vec::new();
+    let mut videos: Vec<SitemapVideo> = Vec::new();
+    let mut news: Vec<SitemapNews> = Vec::new();
+
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(e)) => {
@@ -65,12 +161,18 @@ pub fn parse_sitemap(xml: &str) -> SitemapResult {
                         lastmod = None;
                         changefreq = None;
                         priority = None;
+                        images.clear();
+                        videos.clear();
+                        news.clear();
                     }
                     "sitemap" => {
                         in_sitemap = true;
                         url.clear();
                         lastmod = None;
                     }
+                    "image:image" if in_url => images.push(SitemapImage::default()),
+                    "video:video" if in_url => videos.push(SitemapVideo::default()),
+                    "news:news" if in_url => news.push(SitemapNews::default()),
                     _ => {}
                 }
             }
@@ -83,8 +185,11 @@ pub fn parse_sitemap(xml: &str) -> SitemapResult {
                             result.urls.push(SitemapEntry {
                                 url: url.clone(),
                                 lastmod: lastmod.clone(),
-                                changefreq: changefreq.clone(),
+                                changefreq,
                                 priority,
+                                images: std::mem::take(&mut images),
+                                videos: std::mem::take(&mut videos),
+                                news: std::mem::take(&mut news),
                             });
                         }
                         in_url = false;
@@ -109,8 +214,56 @@ pub fn parse_sitemap(xml: &str) -> SitemapResult {
                     match current_tag.as_str() {
                         "loc" => url = text,
                         "lastmod" => lastmod = Some(text),
-                        "changefreq" if in_url => changefreq = Some(text),
+                        "changefreq" if in_url => changefreq = ChangeFreq::from_str(&text).ok(),
                         "priority" if in_url => priority = text.parse().ok(),
+                        // Image extension children.
+                        "image:loc" => {
+                            if let Some(img) = images.last_mut() {
+                                img.loc = text;
+                            }
+                        }
+                        "image:title" => {
+                            if let Some(img) = images.last_mut() {
+                                img.title = Some(text);
+                            }
+                        }
+                        "image:caption" => {
+                            if let Some(img) = images.last_mut() {
+                                img.caption = Some(text);
+                            }
+                        }
+                        // Video extension children.
+                        "video:content_loc" => {
+                            if let Some(v) = videos.last_mut() {
+                                v.content_loc = Some(text);
+                            }
+                        }
+                        "video:thumbnail_loc" => {
+                            if let Some(v) = videos.last_mut() {
+                                v.thumbnail_loc = Some(text);
+                            }
+                        }
+                        "video:title" => {
+                            if let Some(v) = videos.last_mut() {
+                                v.title = Some(text);
+                            }
+                        }
+                        "video:duration" => {
+                            if let Some(v) = videos.last_mut() {
+                                v.duration = Some(text);
+                            }
+                        }
+                        // News extension children.
+                        "news:publication_date" => {
+                            if let Some(n) = news.last_mut() {
+                                n.publication_date = Some(text);
+                            }
+                        }
+                        "news:title" => {
+                            if let Some(n) = news.last_mut() {
+                                n.title = Some(text);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -128,13 +281,182 @@ pub fn parse_sitemap(xml: &str) -> SitemapResult {
     result
 }
 
+/// Serialize a list of URL entries into a `<urlset>` sitemap document.
+///
+/// Element naming and namespace mirror what [`parse_sitemap`] reads, so
+/// `parse_sitemap(&write_sitemap(xs))` recovers the same URLs. `priority`
+/// is clamped to the `0.0..=1.0` range the schema allows and emitted with
+/// one decimal; `lastmod` is written verbatim (callers are expected to pass
+/// a W3C datetime).
+pub fn write_sitemap(entries: &[SitemapEntry]) -> String {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let _ = writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)));
+
+    let mut urlset = BytesStart::new("urlset");
+    urlset.push_attribute(("xmlns", SITEMAP_NS));
+    let _ = writer.write_event(Event::Start(urlset));
+
+    for entry in entries {
+        let _ = writer.write_event(Event::Start(BytesStart::new("url")));
+        write_text_element(&mut writer, "loc", &entry.url);
+        if let Some(lastmod) = &entry.lastmod {
+            write_text_element(&mut writer, "lastmod", lastmod);
+        }
+        if let Some(changefreq) = entry.changefreq {
+            write_text_element(&mut writer, "changefreq", changefreq.as_str());
+        }
+        if let Some(priority) = entry.priority {
+            let clamped = priority.clamp(0.0, 1.0);
+            write_text_element(&mut writer, "priority", &format!("{:.1}", clamped));
+        }
+        let _ = writer.write_event(Event::End(BytesEnd::new("url")));
+    }
+
+    let _ = writer.write_event(Event::End(BytesEnd::new("urlset")));
+    finish(writer)
+}
+
+/// Serialize a list of child-sitemap references into a `<sitemapindex>` document.
+pub fn write_sitemap_index(entries: &[SitemapIndexEntry]) -> String {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let _ = writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)));
+
+    let mut index = BytesStart::new("sitemapindex");
+    index.push_attribute(("xmlns", SITEMAP_NS));
+    let _ = writer.write_event(Event::Start(index));
+
+    for entry in entries {
+        let _ = writer.write_event(Event::Start(BytesStart::new("sitemap")));
+        write_text_element(&mut writer, "loc", &entry.url);
+        if let Some(lastmod) = &entry.lastmod {
+            write_text_element(&mut writer, "lastmod", lastmod);
+        }
+        let _ = writer.write_event(Event::End(BytesEnd::new("sitemap")));
+    }
+
+    let _ = writer.write_event(Event::End(BytesEnd::new("sitemapindex")));
+    finish(writer)
+}
+
+/// Write `<name>text</name>`; `BytesText::new` handles XML entity escaping.
+fn write_text_element(writer: &mut Writer<Cursor<Vec<u8>>>, name: &str, text: &str) {
+    let _ = writer.write_event(Event::Start(BytesStart::new(name)));
+    let _ = writer.write_event(Event::Text(BytesText::new(text)));
+    let _ = writer.write_event(Event::End(BytesEnd::new(name)));
+}
+
+/// Consume a writer and return its buffer as a UTF-8 string.
+fn finish(writer: Writer<Cursor<Vec<u8>>>) -> String {
+    String::from_utf8(writer.into_inner().into_inner()).unwrap_or_default()
+}
+
+/// Decode a fetched sitemap body to UTF-8 text, transparently gunzipping when
+/// the bytes look gzip-compressed.
+///
+/// Gzip is detected from the magic bytes (`0x1f 0x8b`), a gzip
+/// `Content-Encoding`, or a `.gz` URL suffix; anything else is treated as
+/// plain UTF-8.
+fn decode_body(bytes: &[u8], url: &str, content_encoding: Option<&str>) -> std::io::Result<String> {
+    let looks_gzip = bytes.len() >= 2 && bytes[0] == 0x1f && bytes[1] == 0x8b;
+    let encoded_gzip = content_encoding.map(|e| e.contains("gzip")).unwrap_or(false);
+    let gz_suffix = url.split('?').next().unwrap_or(url).ends_with(".gz");
+
+    if looks_gzip || encoded_gzip || gz_suffix {
+        use std::io::Read;
+        let mut decoder = flate2::read::GzDecoder::new(bytes);
+        let mut text = String::new();
+        decoder.read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Monotonic-clock token bucket for pacing requests to a single host.
+///
+/// `tokens` accumulate at `rate` per second up to `burst`; each request
+/// consumes one token, sleeping just long enough when the bucket is empty.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: std::time::Instant,
+    rate: f64,
+    burst: f64,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            tokens: burst,
+            last_refill: std::time::Instant::now(),
+            rate,
+            burst,
+        }
+    }
+
+    /// Block until a token is available, then consume it.
+    fn acquire(&mut self) {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        self.last_refill = std::time::Instant::now();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+
+        if self.tokens < 1.0 && self.rate > 0.0 {
+            let wait = (1.0 - self.tokens) / self.rate;
+            std::thread::sleep(std::time::Duration::from_secs_f64(wait));
+            self.tokens = 1.0;
+        }
+
+        self.tokens -= 1.0;
+    }
+}
+
+/// State threaded through a polite recursive sitemap crawl.
+struct FetchContext {
+    agent: ureq::Agent,
+    user_agent: String,
+    recursive: bool,
+    max_depth: usize,
+    requests_per_second: f64,
+    burst: f64,
+    respect_crawl_delay: bool,
+    robots: crate::robots::RobotsCache,
+    buckets: std::collections::HashMap<String, TokenBucket>,
+}
+
+impl FetchContext {
+    /// Pace access to `host`, honoring a robots `Crawl-delay` if present.
+    fn throttle(&mut self, host: &str, crawl_delay: Option<f64>) {
+        let rate = match crawl_delay {
+            Some(delay) if self.respect_crawl_delay && delay > 0.0 => {
+                // Use the slower of configured rate and 1/crawl_delay.
+                self.requests_per_second.min(1.0 / delay)
+            }
+            _ => self.requests_per_second,
+        };
+        let burst = self.burst;
+        self.buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(rate, burst))
+            .acquire();
+    }
+}
+
 /// Fetch and parse sitemap(s) using ureq (simple blocking HTTP)
+///
+/// `requests_per_second` and `burst` configure a per-host token bucket so
+/// child sitemaps aren't hammered. Each URL is checked against its host's
+/// `robots.txt`: URLs disallowed by robots rules are skipped and noted in
+/// [`SitemapResult::errors`], and when `respect_crawl_delay` is set the
+/// effective rate is the slower of the configured rate and the host's
+/// `Crawl-delay`.
 pub fn fetch_sitemap_blocking(
     url: &str,
     user_agent: &str,
     timeout_secs: u64,
     recursive: bool,
     max_depth: usize,
+    requests_per_second: f64,
+    burst: f64,
+    respect_crawl_delay: bool,
 ) -> SitemapResult {
     let agent = ureq::Agent::new_with_config(
         ureq::Agent::config_builder()
@@ -143,14 +465,28 @@ pub fn fetch_sitemap_blocking(
             .build(),
     );
 
-    fetch_sitemap_internal_ureq(&agent, url, recursive, max_depth, 0)
+    let mut ctx = FetchContext {
+        agent,
+        user_agent: user_agent.to_string(),
+        recursive,
+        max_depth,
+        requests_per_second: if requests_per_second > 0.0 {
+            requests_per_second
+        } else {
+            1.0
+        },
+        burst: burst.max(1.0),
+        respect_crawl_delay,
+        robots: crate::robots::RobotsCache::new(),
+        buckets: std::collections::HashMap::new(),
+    };
+
+    fetch_sitemap_internal_ureq(&mut ctx, url, 0)
 }
 
 fn fetch_sitemap_internal_ureq(
-    agent: &ureq::Agent,
+    ctx: &mut FetchContext,
     url: &str,
-    recursive: bool,
-    max_depth: usize,
     current_depth: usize,
 ) -> SitemapResult {
     let mut result = SitemapResult {
@@ -159,16 +495,55 @@ fn fetch_sitemap_internal_ureq(
         errors: vec![],
     };
 
-    if current_depth > max_depth {
+    if current_depth > ctx.max_depth {
         return result;
     }
 
+    // Determine host for per-host pacing / robots rules.
+    let host = url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_lowercase()));
+
+    // Consult robots.txt: skip disallowed URLs, pick up any Crawl-delay.
+    let crawl_delay = if let Some(host) = &host {
+        let check = ctx
+            .robots
+            .check_blocking(&ctx.agent, url, &ctx.user_agent);
+        if !check.allowed {
+            result
+                .errors
+                .push(format!("Skipped {} (disallowed by robots.txt)", url));
+            return result;
+        }
+        ctx.throttle(host, check.crawl_delay);
+        check.crawl_delay
+    } else {
+        None
+    };
+    let _ = crawl_delay;
+
     // Fetch the sitemap
-    let xml = match agent.get(url).call() {
+    let xml = match ctx.agent.get(url).call() {
         Ok(resp) => {
             if resp.status().is_success() {
-                match resp.into_body().read_to_string() {
-                    Ok(text) => text,
+                // A large fraction of real sitemaps are gzip-compressed, either
+                // via Content-Encoding or simply served as `.xml.gz`. Read the
+                // raw bytes and decode transparently before XML parsing.
+                let content_encoding = resp
+                    .headers()
+                    .get("content-encoding")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|s| s.to_lowercase());
+                match resp.into_body().read_to_vec() {
+                    Ok(bytes) => match decode_body(&bytes, url, content_encoding.as_deref()) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            result
+                                .errors
+                                .push(format!("Failed to decode {}: {}", url, e));
+                            return result;
+                        }
+                    },
                     Err(e) => {
                         result
                             .errors
@@ -197,15 +572,10 @@ fn fetch_sitemap_internal_ureq(
     result.errors.extend(parsed.errors);
 
     // If recursive, fetch child sitemaps
-    if recursive && !parsed.sitemaps.is_empty() {
+    if ctx.recursive && !parsed.sitemaps.is_empty() {
         for sitemap_entry in parsed.sitemaps {
-            let child_result = fetch_sitemap_internal_ureq(
-                agent,
-                &sitemap_entry.url,
-                recursive,
-                max_depth,
-                current_depth + 1,
-            );
+            let child_result =
+                fetch_sitemap_internal_ureq(ctx, &sitemap_entry.url, current_depth + 1);
             result.urls.extend(child_result.urls);
             result.sitemaps.extend(child_result.sitemaps);
             result.errors.extend(child_result.errors);
@@ -261,4 +631,104 @@ mod tests {
         assert_eq!(result.sitemaps.len(), 2);
         assert_eq!(result.sitemaps[0].url, "https://example.com/sitemap1.xml");
     }
+
+    #[test]
+    fn test_write_sitemap_roundtrip() {
+        let entries = vec![
+            SitemapEntry {
+                url: "https://example.com/a?x=1&y=2".to_string(),
+                lastmod: Some("2024-01-15".to_string()),
+                changefreq: Some(ChangeFreq::Daily),
+                priority: Some(0.8),
+                images: vec![],
+                videos: vec![],
+                news: vec![],
+            },
+            SitemapEntry {
+                url: "https://example.com/b".to_string(),
+                lastmod: None,
+                changefreq: None,
+                priority: None,
+                images: vec![],
+                videos: vec![],
+                news: vec![],
+            },
+        ];
+
+        let xml = write_sitemap(&entries);
+        // Ampersand in the URL must be escaped so the document stays well-formed.
+        assert!(xml.contains("x=1&amp;y=2"));
+
+        let parsed = parse_sitemap(&xml);
+        assert_eq!(parsed.urls.len(), 2);
+        assert_eq!(parsed.urls[0].url, "https://example.com/a?x=1&y=2");
+        assert_eq!(parsed.urls[0].changefreq, Some(ChangeFreq::Daily));
+        assert_eq!(parsed.urls[0].priority, Some(0.8));
+        assert_eq!(parsed.urls[1].url, "https://example.com/b");
+    }
+
+    #[test]
+    fn test_priority_clamped() {
+        let entries = vec![SitemapEntry {
+            url: "https://example.com/".to_string(),
+            lastmod: None,
+            changefreq: None,
+            priority: Some(5.0),
+            images: vec![],
+            videos: vec![],
+            news: vec![],
+        }];
+        let xml = write_sitemap(&entries);
+        assert!(xml.contains("<priority>1.0</priority>"));
+    }
+
+    #[test]
+    fn test_parse_image_video_news_extensions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                xmlns:image="http://www.google.com/schemas/sitemap-image/1.1"
+                xmlns:video="http://www.google.com/schemas/sitemap-video/1.1"
+                xmlns:news="http://www.google.com/schemas/sitemap-news/0.9">
+            <url>
+                <loc>https://example.com/article</loc>
+                <image:image>
+                    <image:loc>https://example.com/a.jpg</image:loc>
+                    <image:title>Photo</image:title>
+                </image:image>
+                <video:video>
+                    <video:content_loc>https://example.com/v.mp4</video:content_loc>
+                    <video:title>Clip</video:title>
+                    <video:duration>120</video:duration>
+                </video:video>
+                <news:news>
+                    <news:publication_date>2024-01-15</news:publication_date>
+                    <news:title>Breaking</news:title>
+                </news:news>
+            </url>
+        </urlset>"#;
+
+        let result = parse_sitemap(xml);
+        assert_eq!(result.urls.len(), 1);
+        let entry = &result.urls[0];
+        assert_eq!(entry.images.len(), 1);
+        assert_eq!(entry.images[0].loc, "https://example.com/a.jpg");
+        assert_eq!(entry.images[0].title, Some("Photo".to_string()));
+        assert_eq!(entry.videos.len(), 1);
+        assert_eq!(entry.videos[0].title, Some("Clip".to_string()));
+        assert_eq!(entry.videos[0].duration, Some("120".to_string()));
+        assert_eq!(entry.news.len(), 1);
+        assert_eq!(entry.news[0].title, Some("Breaking".to_string()));
+    }
+
+    #[test]
+    fn test_write_sitemap_index() {
+        let entries = vec![SitemapIndexEntry {
+            url: "https://example.com/sitemap1.xml".to_string(),
+            lastmod: Some("2024-01-15".to_string()),
+        }];
+        let xml = write_sitemap_index(&entries);
+        let parsed = parse_sitemap(&xml);
+        assert_eq!(parsed.sitemaps.len(), 1);
+        assert_eq!(parsed.sitemaps[0].url, "https://example.com/sitemap1.xml");
+    }
 }